@@ -1,48 +1,812 @@
-use std::time::Instant;
+use std::{
+    any::Any,
+    error::Error,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    str::FromStr,
+    sync::{mpsc, Arc},
+    thread,
+    time::{Duration, Instant},
+};
 
 use dotenv::dotenv;
-use reqwest::{self, Result};
+use serde::{Deserialize, Serialize};
 
+use crate::{
+    http::{self, HttpError},
+    ledger::Ledger,
+};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const USER_AGENT: &str =
+    "https://github.com/FractalBoy/advent-of-code-2023 by reisner.marc@gmail.com";
+
+// Parts are written as 1 and 2 everywhere, the same as on the site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
 pub enum Part {
     Part1,
     Part2,
 }
 
-pub trait Solver {
-    fn day(&self) -> i32;
-    fn solve_part_1(&self, input: &str) -> String;
-    fn solve_part_2(&self, input: &str) -> String;
+impl From<Part> for u8 {
+    fn from(value: Part) -> Self {
+        match value {
+            Part::Part1 => 1,
+            Part::Part2 => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::Part1),
+            2 => Ok(Self::Part2),
+            n => Err(format!("invalid part {}", n)),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Part1 => f.pad("1"),
+            Self::Part2 => f.pad("2"),
+        }
+    }
+}
+
+pub enum InputSource {
+    Puzzle { refresh: bool },
+    Cached,
+    File(PathBuf),
+    Stdin,
 }
 
-async fn get_input_for_day(day: i32) -> Result<String> {
-    let client = reqwest::Client::new();
-    let url = format!("https://adventofcode.com/2023/day/{}/input", day);
+impl From<PathBuf> for InputSource {
+    fn from(value: PathBuf) -> Self {
+        if value.as_os_str() == "-" {
+            Self::Stdin
+        } else {
+            Self::File(value)
+        }
+    }
+}
 
-    dotenv().expect("Failed to load .env file");
-    let session_id = std::env::var("SESSION_ID").expect("Failed to get SESSION_ID");
+// Every request tied to an account needs the session cookie. The errors of
+// those requests say what it was needed for.
+#[derive(Debug)]
+pub struct MissingSessionId;
 
-    client
-        .get(url)
-        .header("Cookie", format!("session={}", session_id))
-        .header(
-            "User-Agent",
-            "https://github.com/FractalBoy/advent-of-code-2023 by reisner.marc@gmail.com",
+impl Display for MissingSessionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "SESSION_ID is not set (in the environment or a .env file)"
         )
-        .send()
-        .await?
-        .text()
-        .await
-}
-
-pub async fn solve(part: Part, solver: &Box<dyn Solver>) -> Result<()> {
-    get_input_for_day(solver.day()).await.map(|input| {
-        let start = Instant::now();
-        let solution = match part {
-            Part::Part1 => solver.solve_part_1(&input),
-            Part::Part2 => solver.solve_part_2(&input),
-        };
-        let duration = start.elapsed();
-        println!("{}", solution);
-        println!("Solution took {} seconds", duration.as_secs_f64());
+    }
+}
+
+impl Error for MissingSessionId {}
+
+#[derive(Debug)]
+pub enum InputError {
+    Session(MissingSessionId),
+    NotCached(PathBuf),
+    Read(PathBuf, io::Error),
+    Write(PathBuf, io::Error),
+    Stdin(io::Error),
+    Http(HttpError),
+    PuzzleHttp(HttpError),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Session(err) => write!(f, "{} and the input needs to be downloaded", err),
+            Self::NotCached(path) => write!(
+                f,
+                "{} does not exist and downloading is disabled in offline mode",
+                path.display()
+            ),
+            Self::Read(path, err) => write!(f, "Failed to read {}: {}", path.display(), err),
+            Self::Write(path, err) => write!(f, "Failed to write {}: {}", path.display(), err),
+            Self::Stdin(err) => write!(f, "Failed to read input from stdin: {}", err),
+            Self::Http(err) => write!(f, "Failed to download input: {}", err),
+            Self::PuzzleHttp(err) => write!(f, "Failed to download puzzle page: {}", err),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Session(err) => Some(err),
+            Self::NotCached(_) => None,
+            Self::Read(_, err) | Self::Write(_, err) | Self::Stdin(err) => Some(err),
+            Self::Http(err) | Self::PuzzleHttp(err) => Some(err),
+        }
+    }
+}
+
+impl From<HttpError> for InputError {
+    fn from(value: HttpError) -> Self {
+        Self::Http(value)
+    }
+}
+
+impl From<MissingSessionId> for InputError {
+    fn from(value: MissingSessionId) -> Self {
+        Self::Session(value)
+    }
+}
+
+// Integers are always stored in the smallest variant that holds them, so
+// answers built through From compare equal regardless of the source type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", from = "String")]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(i128),
+    Text(String),
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            Self::Integer(n) => Some(*n as i128),
+            Self::BigInteger(n) => Some(*n),
+            Self::Text(_) | Self::Grid(_) => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(n) => n.fmt(f),
+            Self::BigInteger(n) => n.fmt(f),
+            Self::Text(text) => f.pad(text),
+            Self::Grid(rows) => f.pad(&rows.join("\n")),
+        }
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(value: i128) -> Self {
+        match i64::try_from(value) {
+            Ok(n) => Self::Integer(n),
+            Err(_) => Self::BigInteger(value),
+        }
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::from(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_integer!(i32, u32, i64, u64, usize);
+
+impl From<&str> for Answer {
+    // Only canonical numbers become integers, so an answer like "007" is
+    // kept exactly as written.
+    fn from(value: &str) -> Self {
+        if value.contains('\n') {
+            Self::Grid(value.lines().map(|l| l.to_owned()).collect())
+        } else if let Some(n) = value
+            .parse::<i128>()
+            .ok()
+            .filter(|n| n.to_string() == value)
+        {
+            Self::from(n)
+        } else {
+            Self::Text(value.to_owned())
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl From<Answer> for String {
+    fn from(value: Answer) -> Self {
+        value.to_string()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SolveError {
+    // Line and column are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    Invalid(String),
+}
+
+impl SolveError {
+    // token must be a slice of source, as returned by lines(), split() etc.
+    pub fn at(source: &str, token: &str, message: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .saturating_sub(source.as_ptr() as usize)
+            .min(source.len());
+        let before = source.get(..offset).unwrap_or(source);
+
+        Self::Parse {
+            line: before.matches('\n').count() + 1,
+            column: before.rsplit('\n').next().unwrap().chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    // Moves an error located within a single line onto that line of the input.
+    pub fn on_line(self, line_idx: usize) -> Self {
+        match self {
+            Self::Parse {
+                line,
+                column,
+                message,
+            } => Self::Parse {
+                line: line + line_idx,
+                column,
+                message,
+            },
+            err => err,
+        }
+    }
+
+    pub fn render(&self, input: &str) -> String {
+        match self {
+            Self::Parse { line, column, .. } => {
+                let text = input.lines().nth(line - 1).unwrap_or("");
+                let gutter = " ".repeat(line.to_string().len());
+
+                format!(
+                    "{}\n{} | {}\n{} | {}^",
+                    self,
+                    line,
+                    text,
+                    gutter,
+                    " ".repeat(column - 1)
+                )
+            }
+            Self::Invalid(_) => self.to_string(),
+        }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            Self::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+pub fn parse_number<T: FromStr>(source: &str, token: &str) -> Result<T, SolveError> {
+    token
+        .parse()
+        .map_err(|_| SolveError::at(source, token, format!("invalid number {:?}", token)))
+}
+
+// Solvers are shared across threads when days run in parallel.
+pub trait Solver: Send + Sync {
+    type Input: Send + Sync;
+
+    fn year(&self) -> i32;
+    fn day(&self) -> i32;
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError>;
+    fn solve_part_1(&self, input: &Self::Input) -> Result<Answer, SolveError>;
+    fn solve_part_2(&self, input: &Self::Input) -> Result<Answer, SolveError>;
+}
+
+// Object-safe view of a Solver, so solvers with different input types can
+// share a dispatch table. The parsed input is passed around type-erased.
+pub trait Puzzle: Send + Sync {
+    fn year(&self) -> i32;
+    fn day(&self) -> i32;
+    fn parse_input(&self, input: &str) -> Result<Box<dyn Any + Send + Sync>, SolveError>;
+    fn solve(&self, part: Part, input: &dyn Any) -> Result<Answer, SolveError>;
+}
+
+impl<S> Puzzle for S
+where
+    S: Solver,
+    S::Input: 'static,
+{
+    fn year(&self) -> i32 {
+        Solver::year(self)
+    }
+
+    fn day(&self) -> i32 {
+        Solver::day(self)
+    }
+
+    fn parse_input(&self, input: &str) -> Result<Box<dyn Any + Send + Sync>, SolveError> {
+        Ok(Box::new(self.parse(input)?))
+    }
+
+    fn solve(&self, part: Part, input: &dyn Any) -> Result<Answer, SolveError> {
+        let input = input
+            .downcast_ref()
+            .expect("input was parsed by a different solver");
+
+        match part {
+            Part::Part1 => self.solve_part_1(input),
+            Part::Part2 => self.solve_part_2(input),
+        }
+    }
+}
+
+pub fn input_cache_path(year: i32, day: i32) -> PathBuf {
+    PathBuf::from(format!("inputs/{}/day{:02}.txt", year, day))
+}
+
+pub fn session_id() -> Option<String> {
+    // A missing .env file is fine as long as SESSION_ID is set some other way.
+    dotenv().ok();
+    std::env::var("SESSION_ID").ok()
+}
+
+pub fn require_session_id() -> Result<String, MissingSessionId> {
+    session_id().ok_or(MissingSessionId)
+}
+
+async fn download_input_for_day(year: i32, day: i32) -> Result<String, InputError> {
+    let session_id = require_session_id()?;
+    let url = format!("{}/{}/day/{}/input", BASE_URL, year, day);
+
+    Ok(http::get(&url, Some(&session_id)).await?)
+}
+
+pub(crate) fn read_file(path: PathBuf) -> Result<String, InputError> {
+    fs::read_to_string(&path).map_err(|err| InputError::Read(path, err))
+}
+
+pub(crate) fn write_file(path: PathBuf, contents: &str) -> Result<(), InputError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|err| InputError::Write(dir.to_owned(), err))?;
+    }
+    fs::write(&path, contents).map_err(|err| InputError::Write(path, err))
+}
+
+pub fn read_cached_input_for_day(year: i32, day: i32) -> Result<String, InputError> {
+    let path = input_cache_path(year, day);
+
+    if !path.exists() {
+        return Err(InputError::NotCached(path));
+    }
+
+    read_file(path)
+}
+
+async fn get_input_for_day(year: i32, day: i32, refresh: bool) -> Result<String, InputError> {
+    let path = input_cache_path(year, day);
+
+    if !refresh && path.exists() {
+        return read_file(path);
+    }
+
+    let input = download_input_for_day(year, day).await?;
+    write_file(path, &input)?;
+
+    Ok(input)
+}
+
+pub async fn get_input(year: i32, day: i32, source: &InputSource) -> Result<String, InputError> {
+    match source {
+        InputSource::Puzzle { refresh } => get_input_for_day(year, day, *refresh).await,
+        InputSource::Cached => read_cached_input_for_day(year, day),
+        InputSource::File(path) => read_file(path.clone()),
+        InputSource::Stdin => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(InputError::Stdin)?;
+            Ok(input)
+        }
+    }
+}
+
+// The input is shared so a part can be solved on a thread that outlives the
+// caller, see run_with_timeout.
+#[derive(Clone)]
+pub struct Parsed {
+    pub input: Arc<dyn Any + Send + Sync>,
+    pub duration: Duration,
+}
+
+pub fn parse(solver: &dyn Puzzle, input: &str) -> Result<Parsed, SolveError> {
+    let start = Instant::now();
+    let input = solver.parse_input(input)?;
+
+    Ok(Parsed {
+        input: Arc::from(input),
+        duration: start.elapsed(),
+    })
+}
+
+pub struct Solution {
+    pub day: i32,
+    pub part: Part,
+    pub answer: Answer,
+    // Both parts are solved from the same parsed input, so they share
+    // their parse time.
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+pub fn run(part: Part, solver: &dyn Puzzle, parsed: &Parsed) -> Result<Solution, SolveError> {
+    let start = Instant::now();
+    let answer = solver.solve(part, parsed.input.as_ref())?;
+    let solve = start.elapsed();
+
+    Ok(Solution {
+        day: solver.day(),
+        part,
+        answer,
+        parse: parsed.duration,
+        solve,
     })
 }
+
+#[derive(Debug)]
+pub enum RunError {
+    Failed(SolveError),
+    Panicked,
+    TimedOut(Duration),
+}
+
+// Solves a part, catching panics. With a timeout the part runs on its own
+// thread, which is abandoned if it takes too long: threads can't be
+// cancelled, so it keeps running in the background until the process exits.
+pub fn run_with_timeout(
+    part: Part,
+    solver: &Arc<dyn Puzzle>,
+    parsed: &Parsed,
+    timeout: Option<Duration>,
+) -> Result<Solution, RunError> {
+    let result = match timeout {
+        None => panic::catch_unwind(AssertUnwindSafe(|| run(part, solver.as_ref(), parsed))),
+        Some(timeout) => {
+            let (sender, receiver) = mpsc::channel();
+            let (solver, parsed) = (Arc::clone(solver), parsed.clone());
+
+            thread::spawn(move || {
+                let result =
+                    panic::catch_unwind(AssertUnwindSafe(|| run(part, solver.as_ref(), &parsed)));
+                // Nobody is listening any more if the part timed out.
+                let _ = sender.send(result);
+            });
+
+            match receiver.recv_timeout(timeout) {
+                Ok(result) => result,
+                Err(mpsc::RecvTimeoutError::Timeout) => return Err(RunError::TimedOut(timeout)),
+                Err(mpsc::RecvTimeoutError::Disconnected) => return Err(RunError::Panicked),
+            }
+        }
+    };
+
+    match result {
+        Ok(Ok(solution)) => Ok(solution),
+        Ok(Err(err)) => Err(RunError::Failed(err)),
+        Err(_) => Err(RunError::Panicked),
+    }
+}
+
+pub fn report_run_error(day: i32, part: Part, input: &str, err: &RunError) {
+    match err {
+        RunError::Failed(err) => report_error(day, Some(part), input, err),
+        RunError::Panicked => eprintln!("Day {} part {} panicked", day, part),
+        RunError::TimedOut(timeout) => eprintln!(
+            "Day {} part {} timed out after {} seconds",
+            day,
+            part,
+            timeout.as_secs_f64()
+        ),
+    }
+}
+
+// A part of None means the error happened while parsing.
+pub fn report_error(day: i32, part: Option<Part>, input: &str, err: &SolveError) {
+    match part {
+        Some(part) => eprintln!("Day {} part {} failed: {}", day, part, err.render(input)),
+        None => eprintln!("Day {} input failed to parse: {}", day, err.render(input)),
+    }
+}
+
+pub fn parse_or_report(solver: &dyn Puzzle, input: &str) -> Option<Parsed> {
+    match parse(solver, input) {
+        Ok(parsed) => {
+            println!("Parsing took {} seconds", parsed.duration.as_secs_f64());
+            Some(parsed)
+        }
+        Err(err) => {
+            report_error(solver.day(), None, input, &err);
+            None
+        }
+    }
+}
+
+pub fn solve(
+    part: Part,
+    solver: &Arc<dyn Puzzle>,
+    input: &str,
+    parsed: &Parsed,
+    ledger: &Ledger,
+    timeout: Option<Duration>,
+) -> Option<Answer> {
+    let solution = match run_with_timeout(part, solver, parsed, timeout) {
+        Ok(solution) => solution,
+        Err(err) => {
+            report_run_error(solver.day(), part, input, &err);
+            return None;
+        }
+    };
+
+    println!("{}", solution.answer);
+    println!("Solution took {} seconds", solution.solve.as_secs_f64());
+    println!("{}", ledger.check(solution.day, part, &solution.answer));
+
+    Some(solution.answer)
+}
+
+pub fn print_summary(solutions: &[Solution], ledger: &Ledger) {
+    let width = solutions
+        .iter()
+        .map(|s| s.answer.to_string().len())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+
+    println!(
+        "{:>3}  {:>4}  {:<width$}  {:>12}  {:>12}  Ledger",
+        "Day", "Part", "Answer", "Parse (s)", "Solve (s)"
+    );
+
+    for solution in solutions {
+        println!(
+            "{:>3}  {:>4}  {:<width$}  {:>12.6}  {:>12.6}  {}",
+            solution.day,
+            solution.part,
+            solution.answer,
+            solution.parse.as_secs_f64(),
+            solution.solve.as_secs_f64(),
+            ledger
+                .check(solution.day, solution.part, &solution.answer)
+                .summary()
+        );
+    }
+
+    // Count each day's shared parse time once.
+    let mut parse = Duration::ZERO;
+    let mut last_day = None;
+
+    for solution in solutions {
+        if last_day != Some(solution.day) {
+            parse += solution.parse;
+            last_day = Some(solution.day);
+        }
+    }
+
+    let solve: Duration = solutions.iter().map(|s| s.solve).sum();
+    println!(
+        "CPU time: {} seconds summed over every part ({} parsing, {} solving)",
+        (parse + solve).as_secs_f64(),
+        parse.as_secs_f64(),
+        solve.as_secs_f64()
+    );
+}
+
+// One solved part, in the shape written out for scripts.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub year: i32,
+    pub day: i32,
+    pub part: Part,
+    pub answer: Answer,
+    pub parse_seconds: f64,
+    pub solve_seconds: f64,
+    pub verdict: &'static str,
+}
+
+impl Record {
+    pub fn new(year: i32, solution: &Solution, ledger: &Ledger) -> Self {
+        Record {
+            year,
+            day: solution.day,
+            part: solution.part,
+            answer: solution.answer.clone(),
+            parse_seconds: solution.parse.as_secs_f64(),
+            solve_seconds: solution.solve.as_secs_f64(),
+            verdict: ledger
+                .check(solution.day, solution.part, &solution.answer)
+                .status(),
+        }
+    }
+}
+
+pub fn records_json(records: &[Record]) -> String {
+    serde_json::to_string_pretty(records).unwrap()
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+pub fn records_csv(records: &[Record]) -> String {
+    let mut csv = "year,day,part,answer,parse_seconds,solve_seconds,verdict\n".to_owned();
+
+    for record in records {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            record.year,
+            record.day,
+            record.part,
+            csv_field(&record.answer.to_string()),
+            record.parse_seconds,
+            record.solve_seconds,
+            record.verdict
+        ));
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, sync::Arc, thread, time::Duration};
+
+    use super::{
+        parse, parse_number, records_csv, run_with_timeout, Answer, Part, Puzzle, Record, RunError,
+        Solution, SolveError, Solver,
+    };
+    use crate::ledger::Ledger;
+
+    // Sleeps for as many milliseconds as its input says.
+    struct Slow;
+
+    impl Solver for Slow {
+        type Input = u64;
+
+        fn year(&self) -> i32 {
+            2023
+        }
+
+        fn day(&self) -> i32 {
+            1
+        }
+
+        fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+            parse_number(input, input)
+        }
+
+        fn solve_part_1(&self, millis: &Self::Input) -> Result<Answer, SolveError> {
+            thread::sleep(Duration::from_millis(*millis));
+            Ok(Answer::from(*millis))
+        }
+
+        fn solve_part_2(&self, _: &Self::Input) -> Result<Answer, SolveError> {
+            panic!("part 2 is not solved yet");
+        }
+    }
+
+    #[test]
+    fn answers_compare_by_value() {
+        assert_eq!(Answer::from(46u64), Answer::from(46i32));
+        assert_eq!(Answer::from("46"), Answer::Integer(46));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInteger(u64::MAX as i128));
+        assert_eq!(Answer::from("ABC"), Answer::Text("ABC".to_owned()));
+        assert_eq!(Answer::from("007"), Answer::Text("007".to_owned()));
+        assert_eq!(Answer::from("+5"), Answer::Text("+5".to_owned()));
+        assert_eq!(Answer::from("-5"), Answer::Integer(-5));
+        assert_eq!(
+            Answer::from("#.\n.#"),
+            Answer::Grid(vec!["#.".to_owned(), ".#".to_owned()])
+        );
+        assert_eq!(Answer::from("#.\n.#").to_string(), "#.\n.#");
+    }
+
+    #[test]
+    fn serializes_parts_as_numbers() {
+        assert_eq!(serde_json::to_string(&Part::Part2).unwrap(), "2");
+        assert_eq!(serde_json::from_str::<Part>("1").unwrap(), Part::Part1);
+        assert!(serde_json::from_str::<Part>("3").is_err());
+
+        let answers = BTreeMap::from([(Part::Part1, Answer::from(46))]);
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(json, r#"{"1":"46"}"#);
+        assert_eq!(
+            serde_json::from_str::<BTreeMap<Part, Answer>>(&json).unwrap(),
+            answers
+        );
+    }
+
+    #[test]
+    fn writes_records_as_csv() {
+        let solution = Solution {
+            day: 13,
+            part: Part::Part2,
+            answer: Answer::from("#.\n.#"),
+            parse: Duration::from_millis(250),
+            solve: Duration::from_millis(500),
+        };
+        let records = [Record::new(2023, &solution, &Ledger::default())];
+
+        assert_eq!(
+            records_csv(&records),
+            "year,day,part,answer,parse_seconds,solve_seconds,verdict\n2023,13,2,\"#.\n.#\",0.25,0.5,unknown\n"
+        );
+    }
+
+    #[test]
+    fn gives_up_on_slow_parts() {
+        let solver: Arc<dyn Puzzle> = Arc::new(Slow);
+        let timeout = Some(Duration::from_millis(200));
+
+        let parsed = parse(solver.as_ref(), "1").unwrap();
+        assert_eq!(
+            run_with_timeout(Part::Part1, &solver, &parsed, timeout)
+                .unwrap()
+                .answer,
+            Answer::from(1)
+        );
+
+        let parsed = parse(solver.as_ref(), "5000").unwrap();
+        assert!(matches!(
+            run_with_timeout(Part::Part1, &solver, &parsed, timeout),
+            Err(RunError::TimedOut(_))
+        ));
+        assert!(matches!(
+            run_with_timeout(Part::Part2, &solver, &parsed, None),
+            Err(RunError::Panicked)
+        ));
+    }
+
+    #[test]
+    fn locates_parse_errors() {
+        let input = "1 2\n3 x\n";
+        let line = input.lines().nth(1).unwrap();
+        let err = parse_number::<u32>(input, &line[2..]).unwrap_err();
+
+        assert_eq!(err.to_string(), "line 2, column 3: invalid number \"x\"");
+        assert_eq!(
+            err.render(input),
+            "line 2, column 3: invalid number \"x\"\n2 | 3 x\n  |   ^"
+        );
+        assert_eq!(
+            SolveError::at(line, &line[2..], "bad").on_line(1),
+            SolveError::at(input, &line[2..], "bad")
+        );
+    }
+}
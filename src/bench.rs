@@ -0,0 +1,393 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::aoc::{self, Answer, Parsed, Part, Puzzle, SolveError};
+
+pub fn history_path(year: i32) -> PathBuf {
+    PathBuf::from(format!("benchmarks/{}.jsonl", year))
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl From<&[Duration]> for Stats {
+    fn from(value: &[Duration]) -> Self {
+        let mut secs: Vec<f64> = value.iter().map(|d| d.as_secs_f64()).collect();
+        secs.sort_by(|a, b| a.total_cmp(b));
+
+        let n = secs.len() as f64;
+        let mean = secs.iter().sum::<f64>() / n;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n;
+        let median = if secs.len().is_multiple_of(2) {
+            (secs[secs.len() / 2 - 1] + secs[secs.len() / 2]) / 2.0
+        } else {
+            secs[secs.len() / 2]
+        };
+
+        Stats {
+            min: secs[0],
+            median,
+            mean,
+            stddev: variance.sqrt(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BenchResult {
+    pub day: i32,
+    pub part: Part,
+    pub answer: Answer,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+// Returns the input from the last iteration, for the parts to be
+// benchmarked against.
+pub fn bench_parse(
+    solver: &dyn Puzzle,
+    input: &str,
+    warmup: usize,
+    iterations: usize,
+) -> Result<(Parsed, Stats), SolveError> {
+    for _ in 0..warmup {
+        aoc::parse(solver, input)?;
+    }
+
+    let mut parsed = None;
+    let mut durations = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations.max(1) {
+        let result = aoc::parse(solver, input)?;
+        durations.push(result.duration);
+        parsed = Some(result);
+    }
+
+    Ok((parsed.unwrap(), Stats::from(durations.as_slice())))
+}
+
+pub fn bench(
+    part: Part,
+    solver: &dyn Puzzle,
+    parsed: &Parsed,
+    parse: Stats,
+    warmup: usize,
+    iterations: usize,
+) -> Result<BenchResult, SolveError> {
+    let solve = || solver.solve(part, parsed.input.as_ref());
+
+    for _ in 0..warmup {
+        solve()?;
+    }
+
+    let mut answer = None;
+    let mut durations = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        answer = Some(solve()?);
+        durations.push(start.elapsed());
+    }
+
+    Ok(BenchResult {
+        day: solver.day(),
+        part,
+        answer: answer.unwrap(),
+        iterations: durations.len(),
+        parse,
+        solve: Stats::from(durations.as_slice()),
+    })
+}
+
+pub fn print_results(results: &[BenchResult]) {
+    println!(
+        "{:>3}  {:>4}  {:>12}  {:>12}  {:>12}  {:>12}  {:>12}",
+        "Day", "Part", "Parse (s)", "Min (s)", "Median (s)", "Mean (s)", "Stddev (s)"
+    );
+
+    for result in results {
+        println!(
+            "{:>3}  {:>4}  {:>12.6}  {:>12.6}  {:>12.6}  {:>12.6}  {:>12.6}",
+            result.day,
+            result.part,
+            result.parse.median,
+            result.solve.min,
+            result.solve.median,
+            result.solve.mean,
+            result.solve.stddev
+        );
+    }
+}
+
+pub fn write_json(path: &Path, results: &[BenchResult]) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(results).unwrap();
+
+    if path.as_os_str() == "-" {
+        println!("{}", json);
+        Ok(())
+    } else {
+        fs::write(path, json)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    pub commit: String,
+    pub timestamp: u64,
+    pub day: i32,
+    pub part: Part,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+pub struct Comparison<'a> {
+    pub result: &'a BenchResult,
+    pub baseline: Option<&'a HistoryRecord>,
+    pub change: Option<f64>,
+    pub parse_change: Option<f64>,
+    pub regressed: bool,
+}
+
+pub fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_owned())
+    };
+
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit) if git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty()) => {
+            format!("{}-dirty", commit)
+        }
+        Some(commit) => commit,
+        None => "unknown".to_owned(),
+    }
+}
+
+pub fn load_history(path: &Path) -> io::Result<Vec<HistoryRecord>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(|l| serde_json::from_str(l).map_err(io::Error::from))
+        .collect()
+}
+
+pub fn append_history(path: &Path, commit: &str, results: &[BenchResult]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+
+    for result in results {
+        let record = HistoryRecord {
+            commit: commit.to_owned(),
+            timestamp,
+            day: result.day,
+            part: result.part,
+            iterations: result.iterations,
+            parse: result.parse,
+            solve: result.solve,
+        };
+        writeln!(file, "{}", serde_json::to_string(&record).unwrap())?;
+    }
+
+    Ok(())
+}
+
+// The baseline for each day and part is its most recent record from the
+// given commit, or from any other commit than the current one.
+pub fn compare<'a>(
+    results: &'a [BenchResult],
+    history: &'a [HistoryRecord],
+    current_commit: &str,
+    baseline_commit: Option<&str>,
+    threshold: f64,
+) -> Vec<Comparison<'a>> {
+    results
+        .iter()
+        .map(|result| {
+            let baseline = history
+                .iter()
+                .filter(|r| r.day == result.day && r.part == result.part)
+                .filter(|r| match baseline_commit {
+                    Some(commit) => r.commit.starts_with(commit),
+                    None => r.commit != current_commit,
+                })
+                .max_by_key(|r| r.timestamp);
+
+            let change = baseline.map(|b| result.solve.median / b.solve.median - 1.0);
+            let parse_change = baseline.map(|b| result.parse.median / b.parse.median - 1.0);
+
+            Comparison {
+                result,
+                baseline,
+                change,
+                parse_change,
+                // Parsing and solving are judged separately, so a slower parse
+                // isn't hidden by a faster solve.
+                regressed: [change, parse_change]
+                    .into_iter()
+                    .flatten()
+                    .any(|c| c * 100.0 > threshold),
+            }
+        })
+        .collect()
+}
+
+pub fn render_comparisons(comparisons: &[Comparison]) -> String {
+    let mut lines = vec![format!(
+        "{:>3}  {:>4}  {:>12}  {:>12}  {:>9}  {:>9}  Baseline",
+        "Day", "Part", "Base (s)", "Median (s)", "Change", "Parse"
+    )];
+
+    for comparison in comparisons {
+        let result = comparison.result;
+
+        lines.push(
+            match (
+                comparison.baseline,
+                comparison.change,
+                comparison.parse_change,
+            ) {
+                (Some(baseline), Some(change), Some(parse_change)) => format!(
+                    "{:>3}  {:>4}  {:>12.6}  {:>12.6}  {:>+8.1}%  {:>+8.1}%  {}{}",
+                    result.day,
+                    result.part,
+                    baseline.solve.median,
+                    result.solve.median,
+                    change * 100.0,
+                    parse_change * 100.0,
+                    baseline.commit,
+                    if comparison.regressed {
+                        "  REGRESSED"
+                    } else {
+                        ""
+                    }
+                ),
+                _ => format!(
+                    "{:>3}  {:>4}  {:>12}  {:>12.6}  {:>9}  {:>9}  none",
+                    result.day, result.part, "-", result.solve.median, "-", "-"
+                ),
+            },
+        );
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare, BenchResult, HistoryRecord, Stats};
+    use crate::aoc::{Answer, Part};
+
+    fn stats(median: f64) -> Stats {
+        Stats {
+            min: median,
+            median,
+            mean: median,
+            stddev: 0.0,
+        }
+    }
+
+    #[test]
+    fn computes_stats() {
+        let durations: Vec<Duration> = [4, 1, 3, 2].map(Duration::from_secs).to_vec();
+        let stats = Stats::from(durations.as_slice());
+
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.median, 2.5);
+        assert_eq!(stats.mean, 2.5);
+        assert_eq!(stats.stddev, 1.25f64.sqrt());
+    }
+
+    #[test]
+    fn flags_regressions() {
+        let record = |commit: &str, timestamp, part, median| HistoryRecord {
+            commit: commit.to_owned(),
+            timestamp,
+            day: 5,
+            part,
+            iterations: 10,
+            parse: stats(1.0),
+            solve: stats(median),
+        };
+        let history = [
+            record("aaa", 1, Part::Part1, 1.0),
+            record("bbb", 2, Part::Part1, 2.0),
+            record("ccc", 3, Part::Part1, 0.1),
+            record("bbb", 2, Part::Part2, 1.0),
+        ];
+        let result = |part, median| BenchResult {
+            day: 5,
+            part,
+            answer: Answer::from(0),
+            iterations: 10,
+            parse: stats(1.0),
+            solve: stats(median),
+        };
+        let results = [result(Part::Part1, 2.1), result(Part::Part2, 1.5)];
+
+        let comparisons = compare(&results, &history, "ccc", None, 10.0);
+        assert_eq!(comparisons[0].baseline.unwrap().commit, "bbb");
+        assert!(!comparisons[0].regressed);
+        assert!(comparisons[1].regressed);
+
+        let comparisons = compare(&results, &history, "ccc", Some("aaa"), 10.0);
+        assert!(comparisons[0].regressed);
+        assert!(comparisons[1].baseline.is_none());
+        assert!(!comparisons[1].regressed);
+    }
+
+    #[test]
+    fn flags_parse_regressions() {
+        let history = [HistoryRecord {
+            commit: "aaa".to_owned(),
+            timestamp: 1,
+            day: 5,
+            part: Part::Part1,
+            iterations: 10,
+            parse: stats(1.0),
+            solve: stats(1.0),
+        }];
+        let results = [BenchResult {
+            day: 5,
+            part: Part::Part1,
+            answer: Answer::from(0),
+            iterations: 10,
+            parse: stats(2.0),
+            solve: stats(0.5),
+        }];
+
+        let comparisons = compare(&results, &history, "bbb", None, 10.0);
+        assert_eq!(comparisons[0].change, Some(-0.5));
+        assert_eq!(comparisons[0].parse_change, Some(1.0));
+        assert!(comparisons[0].regressed);
+    }
+}
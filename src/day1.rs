@@ -1,5 +1,6 @@
-use crate::aoc::Solver;
+use crate::aoc::{Answer, SolveError, Solver};
 
+#[derive(Default)]
 pub struct Day1 {}
 
 impl Day1 {
@@ -93,20 +94,30 @@ impl State {
 }
 
 impl Solver for Day1 {
+    type Input = Vec<String>;
+
+    fn year(&self) -> i32 {
+        2023
+    }
+
     fn day(&self) -> i32 {
         1
     }
 
-    fn solve_part_1(&self, input: &str) -> String {
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        Ok(input.lines().map(|l| l.to_owned()).collect())
+    }
+
+    fn solve_part_1(&self, input: &Self::Input) -> Result<Answer, SolveError> {
         let mut sum = 0;
 
-        for line in input.lines() {
+        for line in input {
             let mut first: Option<char> = Option::None;
             let mut last: Option<char> = Option::None;
 
             for char in line.chars() {
                 if char.is_numeric() {
-                    if let None = first {
+                    if first.is_none() {
                         first = Some(char)
                     }
                     if char.is_numeric() {
@@ -126,13 +137,13 @@ impl Solver for Day1 {
             }
         }
 
-        sum.to_string()
+        Ok(sum.into())
     }
 
-    fn solve_part_2(&self, input: &str) -> String {
+    fn solve_part_2(&self, input: &Self::Input) -> Result<Answer, SolveError> {
         let mut sum = 0;
 
-        for line in input.split("\n") {
+        for line in input {
             let mut state = State::NoLetters;
             let mut first: Option<char> = None;
             let mut last: Option<char> = None;
@@ -140,16 +151,14 @@ impl Solver for Day1 {
             for char in line.chars() {
                 state = state.next_state(char);
 
-                let num;
-
-                if char.is_numeric() {
-                    num = Some(char);
+                let num = if char.is_numeric() {
+                    Some(char)
                 } else {
-                    num = state.get_number()
-                }
+                    state.get_number()
+                };
 
                 if let Some(num) = num {
-                    if let None = first {
+                    if first.is_none() {
                         first = Some(num);
                     }
 
@@ -168,26 +177,30 @@ impl Solver for Day1 {
             }
         }
 
-        sum.to_string()
+        Ok(sum.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Day1;
-    use crate::aoc::Solver;
+    use crate::aoc::{Answer, Solver};
 
     #[test]
     fn part_1() {
         let solver = Day1::new();
         assert_eq!(
             solver.solve_part_1(
-                "1abc2
+                &solver
+                    .parse(
+                        "1abc2
 pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet"
+                    )
+                    .unwrap()
             ),
-            "142"
+            Ok(Answer::from(142))
         );
     }
 
@@ -196,15 +209,19 @@ treb7uchet"
         let solver = Day1::new();
         assert_eq!(
             solver.solve_part_2(
-                "two1nine
+                &solver
+                    .parse(
+                        "two1nine
 eightwothree
 abcone2threexyz
 xtwone3four
 4nineeightseven2
 zoneight234
 7pqrstsixteen"
+                    )
+                    .unwrap()
             ),
-            "281"
+            Ok(Answer::from(281))
         );
     }
 }
@@ -1,6 +1,6 @@
 use std::fmt::{Display, Write};
 
-use crate::aoc::Solver;
+use crate::aoc::{Answer, SolveError, Solver};
 
 #[derive(Clone, Copy, PartialEq)]
 enum Tile {
@@ -21,72 +21,75 @@ impl Display for Tile {
     }
 }
 
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Tile {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            'S' => Self::StartingPosition,
-            '|' => Self::Vertical,
-            '-' => Self::Horizontal,
-            'L' => Self::NorthToEast,
-            'J' => Self::NorthToWest,
-            'F' => Self::SouthToEast,
-            '7' => Self::SouthToWest,
-            '.' => Self::Ground,
-            value => unreachable!("{} is not a valid tile character", value.escape_debug()),
+            'S' => Ok(Self::StartingPosition),
+            '|' => Ok(Self::Vertical),
+            '-' => Ok(Self::Horizontal),
+            'L' => Ok(Self::NorthToEast),
+            'J' => Ok(Self::NorthToWest),
+            'F' => Ok(Self::SouthToEast),
+            '7' => Ok(Self::SouthToWest),
+            '.' => Ok(Self::Ground),
+            value => Err(value),
         }
     }
 }
 
-impl Into<char> for Tile {
-    fn into(self) -> char {
-        match self {
-            Self::StartingPosition => 'S',
-            Self::Ground => '.',
-            Self::Vertical => '\u{2502}',
-            Self::Horizontal => '\u{2500}',
-            Self::NorthToWest => '\u{2518}',
-            Self::NorthToEast => '\u{2514}',
-            Self::SouthToEast => '\u{250C}',
-            Self::SouthToWest => '\u{2510}',
+impl From<Tile> for char {
+    fn from(val: Tile) -> Self {
+        match val {
+            Tile::StartingPosition => 'S',
+            Tile::Ground => '.',
+            Tile::Vertical => '\u{2502}',
+            Tile::Horizontal => '\u{2500}',
+            Tile::NorthToWest => '\u{2518}',
+            Tile::NorthToEast => '\u{2514}',
+            Tile::SouthToEast => '\u{250C}',
+            Tile::SouthToWest => '\u{2510}',
         }
     }
 }
 
 #[derive(Clone)]
-struct Map {
+pub struct Map {
     width: usize,
     map: Vec<Tile>,
 }
 
-impl From<&str> for Map {
-    fn from(value: &str) -> Self {
+impl TryFrom<&str> for Map {
+    type Error = SolveError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
         let mut map = Vec::new();
         let mut width = 0;
 
         for line in value.lines() {
             width = 0;
 
-            for char in line.chars() {
+            for (idx, char) in line.char_indices() {
                 width += 1;
-                map.push(char.into());
+                map.push(Tile::try_from(char).map_err(|c| {
+                    SolveError::at(value, &line[idx..], format!("{:?} is not a valid tile", c))
+                })?);
             }
         }
 
-        Self { map, width }
+        Ok(Self { map, width })
     }
 }
 
 impl Display for Map {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut i = 0;
-
-        for tile in &self.map {
-            if i % self.width == 0 {
+        for (i, tile) in self.map.iter().enumerate() {
+            if i.is_multiple_of(self.width) {
                 f.write_char('\n')?;
             }
 
             f.write_char((*tile).into())?;
-            i += 1;
         }
 
         Ok(())
@@ -94,21 +97,43 @@ impl Display for Map {
 }
 
 impl Map {
-    fn get_starting_position(&self) -> usize {
+    fn get_starting_position(&self) -> Result<usize, SolveError> {
         self.map
             .iter()
             .position(|t| *t == Tile::StartingPosition)
-            .unwrap()
+            .ok_or_else(|| SolveError::Invalid("map has no starting position".to_owned()))
+    }
+
+    fn up(&self, pos: usize) -> Option<usize> {
+        pos.checked_sub(self.width)
+    }
+
+    fn down(&self, pos: usize) -> Option<usize> {
+        Some(pos + self.width).filter(|down| *down < self.map.len())
+    }
+
+    fn left(&self, pos: usize) -> Option<usize> {
+        Some(pos).filter(|pos| !pos.is_multiple_of(self.width)).map(|pos| pos - 1)
+    }
+
+    fn right(&self, pos: usize) -> Option<usize> {
+        Some(pos + 1).filter(|right| !right.is_multiple_of(self.width) && *right < self.map.len())
     }
 
-    fn get_next_positions(&self, pos: usize) -> (usize, usize) {
-        match self.map[pos] {
+    fn broken(&self, pos: usize) -> SolveError {
+        SolveError::Invalid(format!(
+            "the loop is broken at row {}, column {}",
+            pos / self.width + 1,
+            pos % self.width + 1
+        ))
+    }
+
+    fn get_next_positions(&self, pos: usize) -> Result<(usize, usize), SolveError> {
+        let next_positions = match self.map[pos] {
             Tile::StartingPosition => {
                 let mut next_positions = Vec::new();
 
-                if pos % self.width > 0 {
-                    let left = pos - 1;
-
+                if let Some(left) = self.left(pos) {
                     match self.map[left] {
                         Tile::Horizontal | Tile::NorthToEast | Tile::SouthToEast => {
                             next_positions.push(left)
@@ -117,9 +142,7 @@ impl Map {
                     }
                 }
 
-                if pos % self.width < self.width - 1 {
-                    let right = pos + 1;
-
+                if let Some(right) = self.right(pos) {
                     match self.map[right] {
                         Tile::Horizontal | Tile::NorthToWest | Tile::SouthToWest => {
                             next_positions.push(right)
@@ -128,9 +151,7 @@ impl Map {
                     }
                 }
 
-                if pos > self.width {
-                    let up = pos - self.width;
-
+                if let Some(up) = self.up(pos) {
                     match self.map[up] {
                         Tile::Vertical | Tile::SouthToEast | Tile::SouthToWest => {
                             next_positions.push(up)
@@ -139,9 +160,7 @@ impl Map {
                     }
                 }
 
-                if pos + self.width < self.map.len() {
-                    let down = pos + self.width;
-
+                if let Some(down) = self.down(pos) {
                     match self.map[down] {
                         Tile::Vertical | Tile::NorthToEast | Tile::NorthToWest => {
                             next_positions.push(down)
@@ -150,44 +169,49 @@ impl Map {
                     }
                 }
 
-                (next_positions[0], next_positions[1])
-            }
-            Tile::Vertical => {
-                let up = pos - self.width;
-                let down = pos + self.width;
-                (up, down)
-            }
-            Tile::Horizontal => {
-                let left = pos - 1;
-                let right = pos + 1;
-                (left, right)
-            }
-            Tile::NorthToEast => {
-                let up = pos - self.width;
-                let right = pos + 1;
-                (up, right)
-            }
-            Tile::NorthToWest => {
-                let up = pos - self.width;
-                let left = pos - 1;
-                (up, left)
-            }
-            Tile::SouthToEast => {
-                let down = pos + self.width;
-                let right = pos + 1;
-                (down, right)
-            }
-            Tile::SouthToWest => {
-                let down = pos + self.width;
-                let left = pos - 1;
-                (down, left)
+                if next_positions.len() != 2 {
+                    return Err(SolveError::Invalid(format!(
+                        "starting position connects to {} pipes instead of 2",
+                        next_positions.len()
+                    )));
+                }
+
+                (Some(next_positions[0]), Some(next_positions[1]))
             }
-            _ => unreachable!(),
+            Tile::Vertical => (self.up(pos), self.down(pos)),
+            Tile::Horizontal => (self.left(pos), self.right(pos)),
+            Tile::NorthToEast => (self.up(pos), self.right(pos)),
+            Tile::NorthToWest => (self.up(pos), self.left(pos)),
+            Tile::SouthToEast => (self.down(pos), self.right(pos)),
+            Tile::SouthToWest => (self.down(pos), self.left(pos)),
+            Tile::Ground => return Err(self.broken(pos)),
+        };
+
+        match next_positions {
+            (Some(first), Some(second)) => Ok((first, second)),
+            _ => Err(self.broken(pos)),
+        }
+    }
+
+    // The pipe at pos has to lead back to where the walk came from, or the
+    // walk could run off along pipes that aren't part of the loop.
+    fn follow(
+        &self,
+        pos: usize,
+        prev: usize,
+        (first, second): (usize, usize),
+    ) -> Result<usize, SolveError> {
+        if first == prev {
+            Ok(second)
+        } else if second == prev {
+            Ok(first)
+        } else {
+            Err(self.broken(pos))
         }
     }
 
-    fn walk(&self) -> u32 {
-        let start = self.get_starting_position();
+    fn walk(&self) -> Result<u32, SolveError> {
+        let start = self.get_starting_position()?;
 
         let mut prev_pos1 = start;
         let mut prev_pos2 = start;
@@ -195,7 +219,7 @@ impl Map {
         let mut pos1;
         let mut pos2;
 
-        (pos1, pos2) = self.get_next_positions(start);
+        (pos1, pos2) = self.get_next_positions(start)?;
 
         let mut count = 1;
 
@@ -206,23 +230,11 @@ impl Map {
 
             count += 1;
 
-            let (next_pos1_1, next_pos1_2) = self.get_next_positions(pos1);
-            let (next_pos2_1, next_pos2_2) = self.get_next_positions(pos2);
+            let (next_pos1_1, next_pos1_2) = self.get_next_positions(pos1)?;
+            let (next_pos2_1, next_pos2_2) = self.get_next_positions(pos2)?;
 
-            let next_pos1;
-            let next_pos2;
-
-            if next_pos1_1 == prev_pos1 {
-                next_pos1 = next_pos1_2;
-            } else {
-                next_pos1 = next_pos1_1;
-            }
-
-            if next_pos2_1 == prev_pos2 {
-                next_pos2 = next_pos2_2;
-            } else {
-                next_pos2 = next_pos2_1;
-            }
+            let next_pos1 = self.follow(pos1, prev_pos1, (next_pos1_1, next_pos1_2))?;
+            let next_pos2 = self.follow(pos2, prev_pos2, (next_pos2_1, next_pos2_2))?;
 
             prev_pos1 = pos1;
             prev_pos2 = pos2;
@@ -231,10 +243,11 @@ impl Map {
             pos2 = next_pos2;
         }
 
-        count
+        Ok(count)
     }
 }
 
+#[derive(Default)]
 pub struct Day10 {}
 
 impl Day10 {
@@ -244,16 +257,25 @@ impl Day10 {
 }
 
 impl Solver for Day10 {
+    type Input = Map;
+
+    fn year(&self) -> i32 {
+        2023
+    }
+
     fn day(&self) -> i32 {
         10
     }
 
-    fn solve_part_1(&self, input: &str) -> String {
-        let map: Map = input.into();
-        map.walk().to_string()
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        Map::try_from(input)
     }
 
-    fn solve_part_2(&self, input: &str) -> String {
+    fn solve_part_1(&self, map: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(map.walk()?.into())
+    }
+
+    fn solve_part_2(&self, _map: &Self::Input) -> Result<Answer, SolveError> {
         todo!()
     }
 }
@@ -261,7 +283,7 @@ impl Solver for Day10 {
 #[cfg(test)]
 mod tests {
     use super::Day10;
-    use crate::aoc::Solver;
+    use crate::aoc::{Answer, Solver};
 
     #[test]
     fn part_1() {
@@ -269,72 +291,108 @@ mod tests {
 
         assert_eq!(
             solver.solve_part_1(
-                ".....
+                &solver
+                    .parse(
+                        ".....
 .S-7.
 .|.|.
 .L-J.
-.....",
+....."
+                    )
+                    .unwrap()
             ),
-            "4"
+            Ok(Answer::from(4))
         );
         assert_eq!(
             solver.solve_part_1(
-                "..F7.
+                &solver
+                    .parse(
+                        "..F7.
 .FJ|.
 SJ.L7
 |F--J
-LJ...",
+LJ..."
+                    )
+                    .unwrap()
             ),
-            "8"
+            Ok(Answer::from(8))
         );
     }
 
     #[test]
+    fn rejects_broken_loops() {
+        let solver = Day10::new();
+
+        for (map, broken_at) in [("-S-", "row 1, column 1"), ("S7\n|.", "row 2, column 1")] {
+            assert_eq!(
+                solver
+                    .solve_part_1(&solver.parse(map).unwrap())
+                    .unwrap_err()
+                    .to_string(),
+                format!("the loop is broken at {}", broken_at)
+            );
+        }
+    }
+
+    #[test]
+    #[ignore = "part 2 not implemented"]
     fn part_2() {
         let solver = Day10::new();
         assert_eq!(
             solver.solve_part_2(
-                "...........
-    .S-------7.
-    .|F-----7|.
-    .||.....||.
-    .||.....||.
-    .|L-7.F-J|.
-    .|..|.|..|.
-    .L--J.L--J.
-    ..........."
+                &solver
+                    .parse(
+                        "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+..........."
+                    )
+                    .unwrap()
             ),
-            "4"
+            Ok(Answer::from(4))
         );
         assert_eq!(
             solver.solve_part_2(
-                ".F----7F7F7F7F-7....
-    .|F--7||||||||FJ....
-    .||.FJ||||||||L7....
-    FJL7L7LJLJ||LJ.L-7..
-    L--J.L7...LJS7F-7L7.
-    ....F-J..F7FJ|L7L7L7
-    ....L7.F7||L7|.L7L7|
-    .....|FJLJ|FJ|F7|.LJ
-    ....FJL-7.||.||||...
-    ....L---J.LJ.LJLJ..."
+                &solver
+                    .parse(
+                        ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ..."
+                    )
+                    .unwrap()
             ),
-            "8"
+            Ok(Answer::from(8))
         );
         assert_eq!(
             solver.solve_part_2(
-                "FF7FSF7F7F7F7F7F---7
-    L|LJ||||||||||||F--J
-    FL-7LJLJ||||||LJL-77
-    F--JF--7||LJLJ7F7FJ-
-    L---JF-JLJ.||-FJLJJ7
-    |F|F-JF---7F7-L7L|7|
-    |FFJF7L7F-JF7|JL---7
-    7-L-JL7||F7|L7F-7F7|
-    L.L7LFJ|||||FJL7||LJ
-    L7JLJL-JLJLJL--JLJ.L"
+                &solver
+                    .parse(
+                        "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L"
+                    )
+                    .unwrap()
             ),
-            "10"
+            Ok(Answer::from(10))
         );
     }
 }
@@ -1,4 +1,4 @@
-use crate::aoc::Solver;
+use crate::aoc::{self, Answer, SolveError, Solver};
 use once_cell::sync::Lazy;
 use regex::Regex;
 
@@ -29,19 +29,19 @@ impl Set {
         }
     }
 
-    fn parse(set_str: &str) -> Self {
+    fn parse(line: &str, set_str: &str) -> Result<Self, SolveError> {
         let mut set = Self::new();
 
         for capture in SET_REGEX.captures_iter(set_str) {
-            let count: i32 = capture["count"].parse().unwrap();
+            let count: i32 = aoc::parse_number(line, &capture["count"])?;
             set.set_count(&capture["color"], count)
         }
 
-        set
+        Ok(set)
     }
 }
 
-struct Game {
+pub struct Game {
     id: i32,
     sets: Vec<Set>,
 }
@@ -60,20 +60,24 @@ impl Game {
         self.sets.push(set)
     }
 
-    fn parse(line: &str) -> Self {
-        let id: i32 = GAME_REGEX.captures(line).unwrap()["game"].parse().unwrap();
+    fn parse(line: &str) -> Result<Self, SolveError> {
+        let captures = GAME_REGEX
+            .captures(line)
+            .ok_or_else(|| SolveError::at(line, line, "expected \"Game <id>: \""))?;
+        let id: i32 = aoc::parse_number(line, &captures["game"])?;
         let mut game = Self::new(id);
 
-        let line = GAME_REGEX.replace(line, "");
+        let sets = &line[captures.get(0).unwrap().end()..];
 
-        for set_str in line.split("; ") {
-            game.add_set(Set::parse(set_str));
+        for set_str in sets.split("; ") {
+            game.add_set(Set::parse(line, set_str)?);
         }
 
-        game
+        Ok(game)
     }
 }
 
+#[derive(Default)]
 pub struct Day2 {}
 
 impl Day2 {
@@ -81,38 +85,47 @@ impl Day2 {
         Day2 {}
     }
 
-    fn parse_games(&self, input: &str) -> Vec<Game> {
+    fn parse_games(&self, input: &str) -> Result<Vec<Game>, SolveError> {
         let mut games = Vec::new();
 
-        for line in input.lines() {
+        for (idx, line) in input.lines().enumerate() {
             if line.is_empty() {
                 continue;
             }
 
-            games.push(Game::parse(line))
+            games.push(Game::parse(line).map_err(|e| e.on_line(idx))?)
         }
 
-        games
+        Ok(games)
     }
 }
 
 impl Solver for Day2 {
+    type Input = Vec<Game>;
+
+    fn year(&self) -> i32 {
+        2023
+    }
+
     fn day(&self) -> i32 {
         2
     }
 
-    fn solve_part_1(&self, input: &str) -> String {
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        self.parse_games(input)
+    }
+
+    fn solve_part_1(&self, games: &Self::Input) -> Result<Answer, SolveError> {
         const MAX_RED: i32 = 12;
         const MAX_GREEN: i32 = 13;
         const MAX_BLUE: i32 = 14;
 
-        let games = self.parse_games(input);
         let mut sum = 0;
 
         for game in games {
             let mut possible = true;
 
-            for set in game.sets {
+            for set in &game.sets {
                 if set.red > MAX_RED || set.green > MAX_GREEN || set.blue > MAX_BLUE {
                     possible = false;
                 }
@@ -123,11 +136,10 @@ impl Solver for Day2 {
             }
         }
 
-        sum.to_string()
+        Ok(sum.into())
     }
 
-    fn solve_part_2(&self, input: &str) -> String {
-        let games = self.parse_games(input);
+    fn solve_part_2(&self, games: &Self::Input) -> Result<Answer, SolveError> {
         let mut sum = 0;
 
         for game in games {
@@ -135,7 +147,7 @@ impl Solver for Day2 {
             let mut min_blue = 0;
             let mut min_green = 0;
 
-            for set in game.sets {
+            for set in &game.sets {
                 if set.red > min_red {
                     min_red = set.red
                 }
@@ -151,14 +163,14 @@ impl Solver for Day2 {
             sum += power;
         }
 
-        sum.to_string()
+        Ok(sum.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Day2;
-    use crate::aoc::Solver;
+    use crate::aoc::{Answer, Solver};
 
     const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
@@ -169,12 +181,18 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
     #[test]
     fn part_1() {
         let solver = Day2::new();
-        assert_eq!(solver.solve_part_1(INPUT), "8");
+        assert_eq!(
+            solver.solve_part_1(&solver.parse(INPUT).unwrap()),
+            Ok(Answer::from(8))
+        );
     }
 
     #[test]
     fn part_2() {
         let solver = Day2::new();
-        assert_eq!(solver.solve_part_2(INPUT), "2286")
+        assert_eq!(
+            solver.solve_part_2(&solver.parse(INPUT).unwrap()),
+            Ok(Answer::from(2286))
+        )
     }
 }
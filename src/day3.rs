@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 
-use crate::aoc::Solver;
+use crate::aoc::{self, Answer, SolveError, Solver};
 
+#[derive(Default)]
 pub struct Day3 {}
 
 impl Day3 {
@@ -9,17 +10,46 @@ impl Day3 {
         Day3 {}
     }
 
-    fn parse_engine(&self, input: &str) -> Vec<Vec<char>> {
-        input
-            .lines()
-            .filter(|line| !line.is_empty())
-            .map(|line| line.chars().collect::<Vec<char>>())
-            .collect::<Vec<Vec<char>>>()
+    // The schematic must be rectangular for the neighbour checks, and every
+    // number must fit in an i32 so it can be read back while solving.
+    fn parse_engine(&self, input: &str) -> Result<Vec<Vec<char>>, SolveError> {
+        let mut engine: Vec<Vec<char>> = Vec::new();
+
+        for (idx, line) in input.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+
+            let row: Vec<char> = line.chars().collect();
+
+            if let Some(width) = engine.first().map(Vec::len) {
+                if row.len() != width {
+                    let end = line.char_indices().nth(width).map_or(line.len(), |(i, _)| i);
+                    return Err(SolveError::at(
+                        line,
+                        &line[end..],
+                        format!("expected {} columns, found {}", width, row.len()),
+                    )
+                    .on_line(idx));
+                }
+            }
+
+            for number in line
+                .split(|c: char| !c.is_ascii_digit())
+                .filter(|n| !n.is_empty())
+            {
+                aoc::parse_number::<i32>(line, number).map_err(|e| e.on_line(idx))?;
+            }
+
+            engine.push(row);
+        }
+
+        Ok(engine)
     }
 
     fn check_for_parts(
         &self,
-        engine: &Vec<Vec<char>>,
+        engine: &[Vec<char>],
         start_idx: Option<usize>,
         end_idx: Option<usize>,
         line_idx: usize,
@@ -30,18 +60,18 @@ impl Day3 {
 
             let number: i32 = String::from_iter(&line[start_idx..=end_idx])
                 .parse()
-                .unwrap();
+                .expect("numbers are checked while parsing");
 
             let mut coords = Vec::new();
 
-            for idx in self.check_line(&line, start_idx, end_idx, char_pred) {
+            for idx in self.check_line(line, start_idx, end_idx, char_pred) {
                 coords.push((line_idx, idx));
             }
 
             if line_idx > 0 {
                 let prev_line = &engine[line_idx - 1];
 
-                for idx in self.check_line(&prev_line, start_idx, end_idx, char_pred) {
+                for idx in self.check_line(prev_line, start_idx, end_idx, char_pred) {
                     coords.push((line_idx - 1, idx))
                 }
             }
@@ -49,7 +79,7 @@ impl Day3 {
             if line_idx < engine.len() - 1 {
                 let next_line = &engine[line_idx + 1];
 
-                for idx in self.check_line(&next_line, start_idx, end_idx, char_pred) {
+                for idx in self.check_line(next_line, start_idx, end_idx, char_pred) {
                     coords.push((line_idx + 1, idx));
                 }
             }
@@ -85,9 +115,7 @@ impl Day3 {
 
         let mut idxs = Vec::new();
 
-        for idx in start_idx..=end_idx {
-            let char = line[idx];
-
+        for (idx, &char) in (start_idx..=end_idx).zip(&line[start_idx..=end_idx]) {
             if char_pred(char) {
                 idxs.push(idx);
             }
@@ -98,35 +126,42 @@ impl Day3 {
 }
 
 impl Solver for Day3 {
+    type Input = Vec<Vec<char>>;
+
+    fn year(&self) -> i32 {
+        2023
+    }
+
     fn day(&self) -> i32 {
         3
     }
 
-    fn solve_part_1(&self, input: &str) -> String {
-        let engine = self.parse_engine(input);
-        let mut sum = 0;
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        self.parse_engine(input)
+    }
+
+    fn solve_part_1(&self, engine: &Self::Input) -> Result<Answer, SolveError> {
+        let mut sum: u64 = 0;
 
         for i in 0..engine.len() {
             let line = &engine[i];
 
             let mut start: Option<usize> = None;
             let mut end: Option<usize> = None;
-            let char_pred = |char: char| !char.is_numeric() && char != '.';
-
-            for j in 0..line.len() {
-                let char = line[j];
+            let char_pred = |char: char| !char.is_ascii_digit() && char != '.';
 
-                if char.is_numeric() {
-                    if let None = start {
+            for (j, &char) in line.iter().enumerate() {
+                if char.is_ascii_digit() {
+                    if start.is_none() {
                         start = Some(j);
                     }
 
                     end = Some(j);
                 } else {
                     if let Some((number, _)) =
-                        self.check_for_parts(&engine, start, end, i, &char_pred)
+                        self.check_for_parts(engine, start, end, i, &char_pred)
                     {
-                        sum += number;
+                        sum += number as u64;
                     }
 
                     start = None;
@@ -134,16 +169,15 @@ impl Solver for Day3 {
                 }
             }
 
-            if let Some((number, _)) = self.check_for_parts(&engine, start, end, i, &char_pred) {
-                sum += number;
+            if let Some((number, _)) = self.check_for_parts(engine, start, end, i, &char_pred) {
+                sum += number as u64;
             }
         }
 
-        sum.to_string()
+        Ok(sum.into())
     }
 
-    fn solve_part_2(&self, input: &str) -> String {
-        let engine = self.parse_engine(input);
+    fn solve_part_2(&self, engine: &Self::Input) -> Result<Answer, SolveError> {
         let mut coord_numbers: HashMap<(usize, usize), Vec<i32>> = HashMap::new();
 
         for i in 0..engine.len() {
@@ -153,18 +187,16 @@ impl Solver for Day3 {
             let mut end: Option<usize> = None;
             let char_pred = |char: char| char == '*';
 
-            for j in 0..line.len() {
-                let char = line[j];
-
-                if char.is_numeric() {
-                    if let None = start {
+            for (j, &char) in line.iter().enumerate() {
+                if char.is_ascii_digit() {
+                    if start.is_none() {
                         start = Some(j);
                     }
 
                     end = Some(j);
                 } else {
                     if let Some((number, coords)) =
-                        self.check_for_parts(&engine, start, end, i, &char_pred)
+                        self.check_for_parts(engine, start, end, i, &char_pred)
                     {
                         for coord in coords {
                             if let Some(point) = coord_numbers.get_mut(&coord) {
@@ -180,7 +212,7 @@ impl Solver for Day3 {
                 }
             }
 
-            if let Some((number, coords)) = self.check_for_parts(&engine, start, end, i, &char_pred)
+            if let Some((number, coords)) = self.check_for_parts(engine, start, end, i, &char_pred)
             {
                 for coord in coords {
                     if let Some(point) = coord_numbers.get_mut(&coord) {
@@ -192,22 +224,27 @@ impl Solver for Day3 {
             }
         }
 
-        let mut sum = 0;
+        // Two i32 parts always multiply within a u64, but enough large gear
+        // ratios can still overflow the sum.
+        let mut sum: u64 = 0;
 
         for (_, start_end_numbers) in coord_numbers {
             if start_end_numbers.len() == 2 {
-                sum += start_end_numbers[0] * start_end_numbers[1];
+                let ratio = start_end_numbers[0] as u64 * start_end_numbers[1] as u64;
+                sum = sum.checked_add(ratio).ok_or_else(|| {
+                    SolveError::Invalid("the sum of gear ratios overflows".to_owned())
+                })?;
             }
         }
 
-        sum.to_string()
+        Ok(sum.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Day3;
-    use crate::aoc::Solver;
+    use crate::aoc::{Answer, Solver};
 
     const INPUT: &str = "467..114..
 ...*......
@@ -223,12 +260,38 @@ mod tests {
     #[test]
     fn part_1() {
         let solver = Day3::new();
-        assert_eq!(solver.solve_part_1(INPUT), "4361");
+        assert_eq!(
+            solver.solve_part_1(&solver.parse(INPUT).unwrap()),
+            Ok(Answer::from(4361))
+        );
     }
 
     #[test]
     fn part_2() {
         let solver = Day3::new();
-        assert_eq!(solver.solve_part_2(INPUT), "467835")
+        assert_eq!(
+            solver.solve_part_2(&solver.parse(INPUT).unwrap()),
+            Ok(Answer::from(467835))
+        )
+    }
+
+    #[test]
+    fn rejects_malformed_schematics() {
+        let solver = Day3::new();
+
+        assert_eq!(
+            solver.parse("467..\n...*\n").unwrap_err().to_string(),
+            "line 2, column 5: expected 5 columns, found 4"
+        );
+        assert!(solver.parse("99999999999*\n............").is_err());
+        assert_eq!(
+            solver.solve_part_2(&solver.parse("99999*99999").unwrap()),
+            Ok(Answer::from(9999800001u64))
+        );
+        // Non-ASCII digits are symbols, not parts of numbers.
+        assert_eq!(
+            solver.solve_part_1(&solver.parse("1²3\n...").unwrap()),
+            Ok(Answer::from(4))
+        );
     }
 }
@@ -1,43 +1,45 @@
 use std::collections::HashMap;
 
-use crate::aoc::Solver;
+use crate::aoc::{self, Answer, SolveError, Solver};
 
-struct Card {
+pub struct Card {
     number: u32,
     winning_numbers: Vec<i32>,
     numbers: Vec<i32>,
 }
 
 impl Card {
-    fn parse(line: &str) -> Self {
-        let split1: Vec<&str> = line.split(':').collect();
-        let number: u32 = split1[0]
-            .replace("Card", "")
-            .replace(' ', "")
-            .parse()
-            .unwrap();
-
-        let split2: Vec<&str> = split1[1].split(" | ").collect();
-
-        let mut winning_numbers: Vec<i32> = split2[0]
-            .trim()
+    fn parse(line: &str) -> Result<Self, SolveError> {
+        let (label, all_numbers) = line
+            .split_once(':')
+            .ok_or_else(|| SolveError::at(line, &line[line.len()..], "expected ':'"))?;
+        let number = label
+            .strip_prefix("Card")
+            .ok_or_else(|| SolveError::at(line, label, "expected \"Card\""))?
+            .trim();
+        let number: u32 = aoc::parse_number(line, number)?;
+
+        let (winning_numbers, numbers) = all_numbers
+            .split_once(" | ")
+            .ok_or_else(|| SolveError::at(line, &line[line.len()..], "expected \" | \""))?;
+
+        let mut winning_numbers: Vec<i32> = winning_numbers
             .split_whitespace()
-            .map(|n| n.parse().unwrap())
-            .collect();
+            .map(|n| aoc::parse_number(line, n))
+            .collect::<Result<_, _>>()?;
         winning_numbers.sort();
 
-        let mut numbers: Vec<i32> = split2[1]
-            .trim()
+        let mut numbers: Vec<i32> = numbers
             .split_whitespace()
-            .map(|n| n.parse().unwrap())
-            .collect();
+            .map(|n| aoc::parse_number(line, n))
+            .collect::<Result<_, _>>()?;
         numbers.sort();
 
-        Card {
+        Ok(Card {
             number,
             winning_numbers,
             numbers,
-        }
+        })
     }
 
     fn winning_count(&self) -> u32 {
@@ -59,6 +61,7 @@ impl Card {
     }
 }
 
+#[derive(Default)]
 pub struct Day4 {}
 
 impl Day4 {
@@ -68,15 +71,31 @@ impl Day4 {
 }
 
 impl Solver for Day4 {
+    type Input = Vec<Card>;
+
+    fn year(&self) -> i32 {
+        2023
+    }
+
     fn day(&self) -> i32 {
         4
     }
 
-    fn solve_part_1(&self, input: &str) -> String {
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        let mut cards: Vec<Card> = input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| Card::parse(line).map_err(|e| e.on_line(idx)))
+            .collect::<Result<_, _>>()?;
+        cards.sort_by_key(|a| a.number);
+
+        Ok(cards)
+    }
+
+    fn solve_part_1(&self, cards: &Self::Input) -> Result<Answer, SolveError> {
         let mut score = 0;
 
-        for line in input.lines() {
-            let card = Card::parse(line);
+        for card in cards {
             let count = card.winning_count();
 
             if count != 0 {
@@ -84,14 +103,10 @@ impl Solver for Day4 {
             }
         }
 
-        score.to_string()
+        Ok(score.into())
     }
 
-    fn solve_part_2(&self, input: &str) -> String {
-        let mut cards: Vec<Card> = input.lines().map(|l| Card::parse(l)).collect();
-        cards.sort_by(|a, b| a.number.cmp(&b.number));
-        let cards = cards;
-
+    fn solve_part_2(&self, cards: &Self::Input) -> Result<Answer, SolveError> {
         let mut card_counts: HashMap<u32, u32> =
             HashMap::from_iter(cards.iter().map(|c| (c.number, 1)));
 
@@ -109,14 +124,14 @@ impl Solver for Day4 {
         }
 
         let sum: u32 = card_counts.values().sum();
-        sum.to_string()
+        Ok(sum.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Day4;
-    use crate::aoc::Solver;
+    use crate::aoc::{Answer, Solver};
 
     const INPUT: &str = r"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
@@ -129,12 +144,18 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
     #[test]
     fn part_1() {
         let solver = Day4::new();
-        assert_eq!(solver.solve_part_1(INPUT), "13");
+        assert_eq!(
+            solver.solve_part_1(&solver.parse(INPUT).unwrap()),
+            Ok(Answer::from(13))
+        );
     }
 
     #[test]
     fn part_2() {
         let solver = Day4::new();
-        assert_eq!(solver.solve_part_2(INPUT), "30");
+        assert_eq!(
+            solver.solve_part_2(&solver.parse(INPUT).unwrap()),
+            Ok(Answer::from(30))
+        );
     }
 }
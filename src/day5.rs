@@ -1,19 +1,24 @@
-use crate::aoc::Solver;
+use crate::aoc::{self, Answer, SolveError, Solver};
 use itertools::Itertools;
 
 #[derive(Debug)]
-struct Almanac {
+pub struct Almanac {
     seeds: Vec<u64>,
     maps: Vec<Map>,
 }
 
 impl Almanac {
-    fn parse(input: &str) -> Self {
+    fn parse(input: &str) -> Result<Self, SolveError> {
         let mut split1: Vec<&str> = input.split("\n\n").collect();
 
-        let split2: Vec<&str> = split1.remove(0).split(": ").collect();
-        let seeds: Vec<&str> = split2[1].split(' ').collect();
-        let seeds: Vec<u64> = seeds.iter().map(|s| s.parse().unwrap()).collect();
+        let seeds = split1.remove(0);
+        let (_, seeds) = seeds
+            .split_once(": ")
+            .ok_or_else(|| SolveError::at(input, seeds, "expected \"seeds: \""))?;
+        let seeds: Vec<u64> = seeds
+            .split(' ')
+            .map(|s| aoc::parse_number(input, s))
+            .collect::<Result<_, _>>()?;
 
         let mut maps = Vec::new();
 
@@ -24,6 +29,14 @@ impl Almanac {
             let split3: Vec<&str> = categories.split(' ').collect();
             let split4: Vec<&str> = split3[0].split('-').collect();
 
+            if split4.len() != 3 {
+                return Err(SolveError::at(
+                    input,
+                    categories,
+                    "expected \"<source>-to-<destination> map:\"",
+                ));
+            }
+
             let mut map = Map {
                 source_category: split4[0].to_string(),
                 destination_category: split4[2].to_string(),
@@ -32,17 +45,29 @@ impl Almanac {
 
             for line in lines {
                 let split5: Vec<&str> = line.split(' ').collect();
+
+                if split5.len() != 3 {
+                    return Err(SolveError::at(input, line, "expected three numbers"));
+                }
+
                 map.ranges.push(Range {
-                    source_range_start: split5[1].parse().unwrap(),
-                    destination_range_start: split5[0].parse().unwrap(),
-                    range_length: split5[2].parse().unwrap(),
+                    source_range_start: aoc::parse_number(input, split5[1])?,
+                    destination_range_start: aoc::parse_number(input, split5[0])?,
+                    range_length: aoc::parse_number(input, split5[2])?,
                 })
             }
 
             maps.push(map);
         }
 
-        Almanac { seeds, maps }
+        Ok(Almanac { seeds, maps })
+    }
+
+    fn find_map(&self, source_category: &str) -> Result<&Map, SolveError> {
+        self.maps
+            .iter()
+            .find(|m| m.source_category == source_category)
+            .ok_or_else(|| SolveError::Invalid(format!("No map from {}", source_category)))
     }
 }
 
@@ -161,6 +186,7 @@ struct Range {
     range_length: u64,
 }
 
+#[derive(Default)]
 pub struct Day5 {}
 
 impl Day5 {
@@ -170,24 +196,29 @@ impl Day5 {
 }
 
 impl Solver for Day5 {
+    type Input = Almanac;
+
+    fn year(&self) -> i32 {
+        2023
+    }
+
     fn day(&self) -> i32 {
         5
     }
 
-    fn solve_part_1(&self, input: &str) -> String {
-        let almanac = Almanac::parse(input);
-        let mut min = std::u64::MAX;
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        Almanac::parse(input)
+    }
+
+    fn solve_part_1(&self, almanac: &Self::Input) -> Result<Answer, SolveError> {
+        let mut min = u64::MAX;
 
         for seed in &almanac.seeds {
             let mut curr_num = *seed;
             let mut curr_category = "seed";
 
             while curr_category != "location" {
-                let map = almanac
-                    .maps
-                    .iter()
-                    .find(|m| m.source_category == curr_category)
-                    .unwrap();
+                let map = almanac.find_map(curr_category)?;
 
                 curr_category = &map.destination_category;
                 curr_num = map.map(curr_num);
@@ -198,23 +229,18 @@ impl Solver for Day5 {
             }
         }
 
-        min.to_string()
+        Ok(min.into())
     }
 
-    fn solve_part_2(&self, input: &str) -> String {
-        let almanac = Almanac::parse(input);
-        let mut min = std::u64::MAX;
+    fn solve_part_2(&self, almanac: &Self::Input) -> Result<Answer, SolveError> {
+        let mut min = u64::MAX;
 
         for (start, range_length) in almanac.seeds.iter().tuples() {
             let mut curr_category = "seed";
             let mut ranges = Vec::from([(*start, *range_length)]);
 
             while curr_category != "location" {
-                let map = almanac
-                    .maps
-                    .iter()
-                    .find(|m| m.source_category == curr_category)
-                    .unwrap();
+                let map = almanac.find_map(curr_category)?;
 
                 curr_category = &map.destination_category;
 
@@ -234,14 +260,14 @@ impl Solver for Day5 {
             }
         }
 
-        min.to_string()
+        Ok(min.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Day5;
-    use crate::aoc::Solver;
+    use crate::aoc::{Answer, Solver};
 
     const INPUT: &str = r"seeds: 79 14 55 13
 
@@ -280,12 +306,18 @@ humidity-to-location map:
     #[test]
     fn part_1() {
         let solver = Day5::new();
-        assert_eq!(solver.solve_part_1(INPUT), "35");
+        assert_eq!(
+            solver.solve_part_1(&solver.parse(INPUT).unwrap()),
+            Ok(Answer::from(35))
+        );
     }
 
     #[test]
     fn part_2() {
         let solver = Day5::new();
-        assert_eq!(solver.solve_part_2(INPUT), "46");
+        assert_eq!(
+            solver.solve_part_2(&solver.parse(INPUT).unwrap()),
+            Ok(Answer::from(46))
+        );
     }
 }
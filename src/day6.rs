@@ -1,7 +1,7 @@
-use crate::aoc::Solver;
+use crate::aoc::{self, Answer, SolveError, Solver};
 
 #[derive(Debug)]
-struct Race {
+pub struct Race {
     time: u64,
     record_distance: u64,
 }
@@ -9,29 +9,35 @@ struct Race {
 type Races = Vec<Race>;
 
 impl Race {
-    fn parse_races(input: &str) -> Races {
+    fn parse_races(input: &str) -> Result<Races, SolveError> {
         let lines: Vec<&str> = input.lines().collect();
 
-        lines[0]
-            .split(' ')
-            .skip(1)
-            .filter(|t| !t.is_empty())
-            .map(|t| t.trim().parse().unwrap())
-            .zip(
-                lines[1]
-                    .split(' ')
-                    .skip(1)
-                    .filter(|t| !t.is_empty())
-                    .map(|d| d.trim().parse().unwrap()),
-            )
+        if lines.len() < 2 {
+            return Err(SolveError::Invalid(
+                "Expected a Time line and a Distance line".to_owned(),
+            ));
+        }
+
+        let parse_line = |line: &str| -> Result<Vec<u64>, SolveError> {
+            line.split(' ')
+                .skip(1)
+                .filter(|t| !t.is_empty())
+                .map(|t| aoc::parse_number(input, t.trim()))
+                .collect()
+        };
+
+        Ok(parse_line(lines[0])?
+            .into_iter()
+            .zip(parse_line(lines[1])?)
             .map(|(t, d)| Race {
                 time: t,
                 record_distance: d,
             })
-            .collect()
+            .collect())
     }
 }
 
+#[derive(Default)]
 pub struct Day6 {}
 
 impl Day6 {
@@ -39,7 +45,7 @@ impl Day6 {
         Day6 {}
     }
 
-    fn solve(&self, races: Races) -> u64 {
+    fn solve(&self, races: &[Race]) -> u64 {
         let mut ways = Vec::new();
 
         for race in races {
@@ -75,18 +81,25 @@ impl Day6 {
 }
 
 impl Solver for Day6 {
+    type Input = Races;
+
+    fn year(&self) -> i32 {
+        2023
+    }
+
     fn day(&self) -> i32 {
         6
     }
 
-    fn solve_part_1(&self, input: &str) -> String {
-        let races = Race::parse_races(input);
-        self.solve(races).to_string()
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        Race::parse_races(input)
     }
 
-    fn solve_part_2(&self, input: &str) -> String {
-        let races = Race::parse_races(input);
+    fn solve_part_1(&self, races: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(self.solve(races).into())
+    }
 
+    fn solve_part_2(&self, races: &Self::Input) -> Result<Answer, SolveError> {
         let mut time = String::new();
         let mut distance = String::new();
 
@@ -95,19 +108,20 @@ impl Solver for Day6 {
             distance.push_str(&race.record_distance.to_string());
         }
 
+        let too_large = |_| SolveError::Invalid("The combined race is too long".to_owned());
         let race = Race {
-            time: time.parse().unwrap(),
-            record_distance: distance.parse().unwrap(),
+            time: time.parse().map_err(too_large)?,
+            record_distance: distance.parse().map_err(too_large)?,
         };
 
-        self.solve(Vec::from([race])).to_string()
+        Ok(self.solve(&[race]).into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Day6;
-    use crate::aoc::Solver;
+    use crate::aoc::{Answer, Solver};
 
     const INPUT: &str = r"Time:      7  15   30
 Distance:  9  40  200";
@@ -115,12 +129,18 @@ Distance:  9  40  200";
     #[test]
     fn part_1() {
         let solver = Day6::new();
-        assert_eq!(solver.solve_part_1(INPUT), "288");
+        assert_eq!(
+            solver.solve_part_1(&solver.parse(INPUT).unwrap()),
+            Ok(Answer::from(288))
+        );
     }
 
     #[test]
     fn part_2() {
         let solver = Day6::new();
-        assert_eq!(solver.solve_part_2(INPUT), "71503");
+        assert_eq!(
+            solver.solve_part_2(&solver.parse(INPUT).unwrap()),
+            Ok(Answer::from(71503))
+        );
     }
 }
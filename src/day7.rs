@@ -4,10 +4,19 @@ use std::{
     ops::{Index, IndexMut},
 };
 
-use crate::aoc::Solver;
+use crate::aoc::{self, Answer, SolveError, Solver};
 
 trait Card:
-    Sized + Default + Clone + Copy + core::hash::Hash + PartialEq + Eq + PartialOrd + Ord + From<char>
+    Sized
+    + Default
+    + Clone
+    + Copy
+    + core::hash::Hash
+    + PartialEq
+    + Eq
+    + PartialOrd
+    + Ord
+    + TryFrom<char>
 {
     fn strength(hand: Hand<Self>) -> Strength;
 }
@@ -30,9 +39,11 @@ enum CardPart1 {
     Ace,
 }
 
-impl From<char> for CardPart1 {
-    fn from(value: char) -> Self {
-        match value {
+impl TryFrom<char> for CardPart1 {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Ok(match value {
             '2' => Self::Two,
             '3' => Self::Three,
             '4' => Self::Four,
@@ -46,14 +57,16 @@ impl From<char> for CardPart1 {
             'Q' => Self::Queen,
             'K' => Self::King,
             'A' => Self::Ace,
-            _ => unreachable!(),
-        }
+            _ => return Err(value),
+        })
     }
 }
 
-impl From<char> for CardPart2 {
-    fn from(value: char) -> Self {
-        match value {
+impl TryFrom<char> for CardPart2 {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Ok(match value {
             'J' => Self::Joker,
             '2' => Self::Two,
             '3' => Self::Three,
@@ -67,8 +80,8 @@ impl From<char> for CardPart2 {
             'Q' => Self::Queen,
             'K' => Self::King,
             'A' => Self::Ace,
-            _ => unreachable!(),
-        }
+            _ => return Err(value),
+        })
     }
 }
 
@@ -133,15 +146,27 @@ impl<T: Card> IndexMut<usize> for Hand<T> {
     }
 }
 
-impl<T: Card> From<&str> for Hand<T> {
-    fn from(value: &str) -> Self {
+impl<T: Card> TryFrom<&str> for Hand<T> {
+    type Error = SolveError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
         let mut hand = Hand([Default::default(); 5]);
 
-        for (idx, c) in value.char_indices() {
-            hand[idx] = c.into()
+        if value.chars().count() != 5 {
+            return Err(SolveError::at(
+                value,
+                value,
+                "expected a hand of five cards",
+            ));
+        }
+
+        for (idx, (pos, c)) in value.char_indices().enumerate() {
+            hand[idx] = T::try_from(c).map_err(|_| {
+                SolveError::at(value, &value[pos..], format!("invalid card {:?}", c))
+            })?;
         }
 
-        hand
+        Ok(hand)
     }
 }
 
@@ -181,9 +206,9 @@ enum Strength {
     FiveOfAKind,
 }
 
-impl<T: Card> Into<Strength> for Hand<T> {
-    fn into(self) -> Strength {
-        T::strength(self)
+impl<T: Card> From<Hand<T>> for Strength {
+    fn from(val: Hand<T>) -> Self {
+        T::strength(val)
     }
 }
 
@@ -311,17 +336,28 @@ impl Card for CardPart2 {
 
 struct HandWithBid<T: Card>(Hand<T>, u32);
 
-impl<T: Card> From<&str> for HandWithBid<T> {
-    fn from(value: &str) -> Self {
-        let split: Vec<&str> = value.split(' ').collect();
+// Cards rank differently in each part, so the hands are parsed both ways.
+pub struct Hands {
+    part_1: Vec<HandWithBid<CardPart1>>,
+    part_2: Vec<HandWithBid<CardPart2>>,
+}
+
+impl<T: Card> TryFrom<&str> for HandWithBid<T> {
+    type Error = SolveError;
 
-        let hand: Hand<T> = split[0].into();
-        let bid: u32 = split[1].parse().unwrap();
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (hand, bid) = value
+            .split_once(' ')
+            .ok_or_else(|| SolveError::at(value, &value[value.len()..], "expected a bid"))?;
 
-        HandWithBid(hand, bid)
+        let hand: Hand<T> = hand.try_into()?;
+        let bid: u32 = aoc::parse_number(value, bid)?;
+
+        Ok(HandWithBid(hand, bid))
     }
 }
 
+#[derive(Default)]
 pub struct Day7 {}
 
 impl Day7 {
@@ -329,40 +365,59 @@ impl Day7 {
         Day7 {}
     }
 
-    fn solve<T: Card>(&self, input: &str) -> String {
-        let mut hands_with_bids: Vec<HandWithBid<T>> = input.lines().map(|l| l.into()).collect();
-        hands_with_bids.sort_unstable_by(|h1, h2| h1.0.cmp(&h2.0));
+    fn parse_hands<T: Card>(&self, input: &str) -> Result<Vec<HandWithBid<T>>, SolveError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, l)| HandWithBid::try_from(l).map_err(|e| e.on_line(idx)))
+            .collect()
+    }
+
+    fn solve<T: Card>(&self, hands_with_bids: &[HandWithBid<T>]) -> Result<Answer, SolveError> {
+        let mut hands_with_bids: Vec<&HandWithBid<T>> = hands_with_bids.iter().collect();
+        hands_with_bids.sort_unstable_by_key(|h| h.0);
 
-        let mut rank = 1;
         let mut score = 0;
 
-        for hand in hands_with_bids {
+        for (rank, hand) in (1..).zip(hands_with_bids) {
             score += hand.1 * rank;
-            rank += 1;
         }
 
-        score.to_string()
+        Ok(score.into())
     }
 }
 
 impl Solver for Day7 {
+    type Input = Hands;
+
+    fn year(&self) -> i32 {
+        2023
+    }
+
     fn day(&self) -> i32 {
         7
     }
 
-    fn solve_part_1(&self, input: &str) -> String {
-        self.solve::<CardPart1>(input)
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        Ok(Hands {
+            part_1: self.parse_hands(input)?,
+            part_2: self.parse_hands(input)?,
+        })
+    }
+
+    fn solve_part_1(&self, hands: &Self::Input) -> Result<Answer, SolveError> {
+        self.solve(&hands.part_1)
     }
 
-    fn solve_part_2(&self, input: &str) -> String {
-        self.solve::<CardPart2>(input)
+    fn solve_part_2(&self, hands: &Self::Input) -> Result<Answer, SolveError> {
+        self.solve(&hands.part_2)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Day7;
-    use crate::aoc::Solver;
+    use crate::aoc::{Answer, Solver};
 
     const INPUT: &str = r"32T3K 765
 T55J5 684
@@ -373,12 +428,18 @@ QQQJA 483";
     #[test]
     fn part_1() {
         let solver = Day7::new();
-        assert_eq!(solver.solve_part_1(INPUT), "6440");
+        assert_eq!(
+            solver.solve_part_1(&solver.parse(INPUT).unwrap()),
+            Ok(Answer::from(6440))
+        );
     }
 
     #[test]
     fn part_2() {
         let solver = Day7::new();
-        assert_eq!(solver.solve_part_2(INPUT), "5905");
+        assert_eq!(
+            solver.solve_part_2(&solver.parse(INPUT).unwrap()),
+            Ok(Answer::from(5905))
+        );
     }
 }
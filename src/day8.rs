@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::aoc::Solver;
+use crate::aoc::{Answer, SolveError, Solver};
 
 #[derive(Debug, Clone, Copy)]
 enum Instruction {
@@ -8,12 +8,14 @@ enum Instruction {
     Right,
 }
 
-impl From<char> for Instruction {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Instruction {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            'L' => Self::Left,
-            'R' => Self::Right,
-            _ => unreachable!(),
+            'L' => Ok(Self::Left),
+            'R' => Ok(Self::Right),
+            _ => Err(value),
         }
     }
 }
@@ -21,9 +23,23 @@ impl From<char> for Instruction {
 #[derive(Debug)]
 struct InstructionList(Vec<Instruction>);
 
-impl From<&str> for InstructionList {
-    fn from(value: &str) -> Self {
-        InstructionList(value.chars().map(|ch| ch.into()).collect())
+impl TryFrom<&str> for InstructionList {
+    type Error = SolveError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value
+            .char_indices()
+            .map(|(pos, ch)| {
+                ch.try_into().map_err(|_| {
+                    SolveError::at(
+                        value,
+                        &value[pos..],
+                        format!("invalid instruction {:?}", ch),
+                    )
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(InstructionList)
     }
 }
 
@@ -34,54 +50,72 @@ struct Node {
     right: String,
 }
 
-impl From<&str> for Node {
-    fn from(value: &str) -> Self {
+impl TryFrom<&str> for Node {
+    type Error = SolveError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
         let split: Vec<&str> = value.split(' ').collect();
+
+        if split.len() != 4 {
+            return Err(SolveError::at(
+                value,
+                value,
+                "expected \"<label> = (<left>, <right>)\"",
+            ));
+        }
+
         let label = split[0];
-        let left = split[2].replace('(', "").replace(',', "");
+        let left = split[2].replace(['(', ','], "");
         let right = split[3].replace(')', "");
 
-        Node {
+        Ok(Node {
             label: label.to_owned(),
             left,
             right,
-        }
+        })
     }
 }
 
 #[derive(Debug)]
 struct NodeList(HashMap<String, Node>);
 
-impl From<&str> for NodeList {
-    fn from(value: &str) -> Self {
-        NodeList(HashMap::from_iter(
-            value
-                .lines()
-                .map(|l| l.into())
-                .map(|n: Node| (n.label.clone(), n)),
-        ))
+impl NodeList {
+    fn get(&self, label: &str) -> Result<&Node, SolveError> {
+        self.0
+            .get(label)
+            .ok_or_else(|| SolveError::Invalid(format!("Node {} does not exist", label)))
     }
-}
 
-impl NodeList {
-    fn navigate(&self, node: &Node, instruction: &Instruction) -> &Node {
+    fn navigate(&self, node: &Node, instruction: &Instruction) -> Result<&Node, SolveError> {
         match instruction {
-            Instruction::Left => self.0.get(&node.left).unwrap(),
-            Instruction::Right => self.0.get(&node.right).unwrap(),
+            Instruction::Left => self.get(&node.left),
+            Instruction::Right => self.get(&node.right),
         }
     }
 }
 
 #[derive(Debug)]
-struct InstructionAndNodeList(InstructionList, NodeList);
+pub struct InstructionAndNodeList(InstructionList, NodeList);
+
+impl TryFrom<&str> for InstructionAndNodeList {
+    type Error = SolveError;
 
-impl From<&str> for InstructionAndNodeList {
-    fn from(value: &str) -> Self {
-        let split: Vec<&str> = value.split("\n\n").collect();
-        InstructionAndNodeList(split[0].into(), split[1].into())
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut lines = value.lines().enumerate();
+
+        let instructions = lines.next().map_or("", |(_, l)| l).try_into()?;
+        let mut nodes = HashMap::new();
+
+        for (idx, line) in lines.filter(|(_, l)| !l.is_empty()) {
+            let node = Node::try_from(line).map_err(|e| e.on_line(idx))?;
+            nodes.insert(node.label.clone(), node);
+        }
+
+        Ok(InstructionAndNodeList(instructions, NodeList(nodes)))
     }
 }
 
+#[derive(Default)]
 pub struct Day8 {}
 
 impl Day8 {
@@ -99,20 +133,29 @@ fn gcd(a: u64, b: u64) -> u64 {
 }
 
 impl Solver for Day8 {
+    type Input = InstructionAndNodeList;
+
+    fn year(&self) -> i32 {
+        2023
+    }
+
     fn day(&self) -> i32 {
         8
     }
 
-    fn solve_part_1(&self, input: &str) -> String {
-        let instruction_and_node_list: InstructionAndNodeList = input.into();
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        InstructionAndNodeList::try_from(input)
+    }
+
+    fn solve_part_1(&self, instruction_and_node_list: &Self::Input) -> Result<Answer, SolveError> {
         let instruction_list = &instruction_and_node_list.0;
         let node_list = &instruction_and_node_list.1;
-        let mut node = node_list.0.get("AAA").unwrap();
+        let mut node = node_list.get("AAA")?;
 
         let mut count = 0;
 
         for instruction in instruction_list.0.clone().into_iter().cycle() {
-            node = node_list.navigate(node, &instruction);
+            node = node_list.navigate(node, &instruction)?;
             count += 1;
 
             if node.label == "ZZZ" {
@@ -120,11 +163,10 @@ impl Solver for Day8 {
             }
         }
 
-        count.to_string()
+        Ok(count.into())
     }
 
-    fn solve_part_2(&self, input: &str) -> String {
-        let instruction_and_node_list: InstructionAndNodeList = input.into();
+    fn solve_part_2(&self, instruction_and_node_list: &Self::Input) -> Result<Answer, SolveError> {
         let instruction_list = &instruction_and_node_list.0;
         let node_list = &instruction_and_node_list.1;
         let mut nodes: Vec<&Node> = Vec::new();
@@ -141,7 +183,7 @@ impl Solver for Day8 {
             let mut count = 0;
 
             for instruction in instruction_list.0.clone().into_iter().cycle() {
-                *node = node_list.navigate(node, &instruction);
+                *node = node_list.navigate(node, &instruction)?;
                 count += 1;
 
                 if node.label.ends_with("Z") {
@@ -152,21 +194,23 @@ impl Solver for Day8 {
             lcm = lcm * count / gcd(lcm, count);
         }
 
-        lcm.to_string()
+        Ok(lcm.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Day8;
-    use crate::aoc::Solver;
+    use crate::aoc::{Answer, Solver};
 
     #[test]
     fn part_1() {
         let solver = Day8::new();
         assert_eq!(
             solver.solve_part_1(
-                "RL
+                &solver
+                    .parse(
+                        "RL
 
 AAA = (BBB, CCC)
 BBB = (DDD, EEE)
@@ -175,18 +219,24 @@ DDD = (DDD, DDD)
 EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)"
+                    )
+                    .unwrap()
             ),
-            "2"
+            Ok(Answer::from(2))
         );
         assert_eq!(
             solver.solve_part_1(
-                "LLR
+                &solver
+                    .parse(
+                        "LLR
 
 AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)"
+                    )
+                    .unwrap()
             ),
-            "6"
+            Ok(Answer::from(6))
         );
     }
 
@@ -195,7 +245,9 @@ ZZZ = (ZZZ, ZZZ)"
         let solver = Day8::new();
         assert_eq!(
             solver.solve_part_2(
-                "LR
+                &solver
+                    .parse(
+                        "LR
 
 11A = (11B, XXX)
 11B = (XXX, 11Z)
@@ -205,8 +257,10 @@ ZZZ = (ZZZ, ZZZ)"
 22C = (22Z, 22Z)
 22Z = (22B, 22B)
 XXX = (XXX, XXX)"
+                    )
+                    .unwrap()
             ),
-            "6"
+            Ok(Answer::from(6))
         );
     }
 }
@@ -1,11 +1,17 @@
-use crate::aoc::Solver;
+use crate::aoc::{self, Answer, SolveError, Solver};
 
 #[derive(Debug)]
 struct History(Vec<i32>);
 
-impl From<&str> for History {
-    fn from(value: &str) -> Self {
-        History(value.split(' ').map(|n| n.parse().unwrap()).collect())
+impl TryFrom<&str> for History {
+    type Error = SolveError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value
+            .split(' ')
+            .map(|n| aoc::parse_number(value, n))
+            .collect::<Result<_, _>>()
+            .map(History)
     }
 }
 
@@ -41,14 +47,22 @@ impl History {
     }
 }
 
-struct Histories(Vec<History>);
+pub struct Histories(Vec<History>);
+
+impl TryFrom<&str> for Histories {
+    type Error = SolveError;
 
-impl From<&str> for Histories {
-    fn from(value: &str) -> Self {
-        Histories(value.lines().map(|l| l.into()).collect())
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value
+            .lines()
+            .enumerate()
+            .map(|(idx, l)| History::try_from(l).map_err(|e| e.on_line(idx)))
+            .collect::<Result<_, _>>()
+            .map(Histories)
     }
 }
 
+#[derive(Default)]
 pub struct Day9 {}
 
 impl Day9 {
@@ -58,37 +72,45 @@ impl Day9 {
 }
 
 impl Solver for Day9 {
+    type Input = Histories;
+
+    fn year(&self) -> i32 {
+        2023
+    }
+
     fn day(&self) -> i32 {
         9
     }
 
-    fn solve_part_1(&self, input: &str) -> String {
-        let histories: Histories = input.into();
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        Histories::try_from(input)
+    }
+
+    fn solve_part_1(&self, histories: &Self::Input) -> Result<Answer, SolveError> {
         let mut sum = 0;
 
-        for history in histories.0 {
+        for history in &histories.0 {
             sum += history.get_next_value();
         }
 
-        sum.to_string()
+        Ok(sum.into())
     }
 
-    fn solve_part_2(&self, input: &str) -> String {
-        let histories: Histories = input.into();
+    fn solve_part_2(&self, histories: &Self::Input) -> Result<Answer, SolveError> {
         let mut sum = 0;
 
-        for history in histories.0 {
+        for history in &histories.0 {
             sum += history.get_previous_value();
         }
 
-        sum.to_string()
+        Ok(sum.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Day9;
-    use crate::aoc::Solver;
+    use crate::aoc::{Answer, Solver};
 
     const INPUT: &str = "0 3 6 9 12 15
 1 3 6 10 15 21
@@ -97,12 +119,18 @@ mod tests {
     #[test]
     fn part_1() {
         let solver = Day9::new();
-        assert_eq!(solver.solve_part_1(INPUT), "114");
+        assert_eq!(
+            solver.solve_part_1(&solver.parse(INPUT).unwrap()),
+            Ok(Answer::from(114))
+        );
     }
 
     #[test]
     fn part_2() {
         let solver = Day9::new();
-        assert_eq!(solver.solve_part_2(INPUT), "2");
+        assert_eq!(
+            solver.solve_part_2(&solver.parse(INPUT).unwrap()),
+            Ok(Answer::from(2))
+        );
     }
 }
@@ -0,0 +1,455 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    aoc::{self, Answer, InputError, Part, Puzzle, RunError, SolveError},
+    ledger::Ledger,
+    puzzle, DispatchTable,
+};
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part_1: Option<Answer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part_2: Option<Answer>,
+}
+
+impl Answers {
+    pub fn get(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::Part1 => self.part_1.as_ref(),
+            Part::Part2 => self.part_2.as_ref(),
+        }
+    }
+
+    fn set(&mut self, part: Part, answer: Answer) {
+        match part {
+            Part::Part1 => self.part_1 = Some(answer),
+            Part::Part2 => self.part_2 = Some(answer),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub input: String,
+    pub answers: Answers,
+}
+
+pub fn fixture_dir(year: i32, day: i32) -> PathBuf {
+    PathBuf::from(format!("fixtures/{}/day{:02}", year, day))
+}
+
+static PRE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<pre><code>(?P<code>.*?)</code></pre>").unwrap());
+static EMPHASIZED_CODE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<code><em>(?P<a>.*?)</em></code>|<em><code>(?P<b>.*?)</code></em>").unwrap()
+});
+
+// The first article describes part 1 and the second, once unlocked, part 2.
+// A part's answer is the last emphasized code in its article. It belongs to
+// the first example that article introduces, or to the first example of the
+// page when the article reuses an earlier one.
+pub fn extract_examples(html: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = Vec::new();
+
+    for (body, part) in puzzle::articles(html).zip([Part::Part1, Part::Part2]) {
+        let mut first_new = None;
+
+        for block in PRE_REGEX.captures_iter(body) {
+            let input = puzzle::strip_tags(&block["code"]);
+
+            if examples.iter().any(|e| e.input == input) {
+                continue;
+            }
+
+            first_new.get_or_insert(examples.len());
+            examples.push(Example {
+                input,
+                answers: Answers::default(),
+            });
+        }
+
+        let answer = EMPHASIZED_CODE_REGEX
+            .captures_iter(body)
+            .last()
+            .and_then(|c| c.name("a").or(c.name("b")))
+            .map(|m| Answer::from(puzzle::strip_tags(m.as_str())));
+
+        let target = first_new.or((!examples.is_empty()).then_some(0));
+
+        if let (Some(target), Some(answer)) = (target, answer) {
+            examples[target].answers.set(part, answer);
+        }
+    }
+
+    examples
+}
+
+pub enum Written {
+    Created(PathBuf),
+    Skipped(PathBuf),
+}
+
+// Existing files are kept unless force is set, since answers are often
+// corrected by hand after extraction.
+pub fn write_examples(dir: &Path, examples: &[Example], force: bool) -> io::Result<Vec<Written>> {
+    fs::create_dir_all(dir)?;
+
+    let mut written = Vec::new();
+
+    for (idx, example) in examples.iter().enumerate() {
+        let name = format!("example{}", idx + 1);
+        let answers = serde_json::to_string_pretty(&example.answers).unwrap() + "\n";
+
+        for (path, contents) in [
+            (dir.join(format!("{}.txt", name)), &example.input),
+            (dir.join(format!("{}.json", name)), &answers),
+        ] {
+            if path.exists() && !force {
+                written.push(Written::Skipped(path));
+            } else {
+                fs::write(&path, contents)?;
+                written.push(Written::Created(path));
+            }
+        }
+    }
+
+    Ok(written)
+}
+
+pub struct Fixture {
+    pub name: String,
+    pub input: String,
+    pub answers: Answers,
+}
+
+fn read(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path)
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))
+}
+
+// Every NAME.txt in dir is a fixture, with its expected answers in NAME.json.
+// A fixture without answers is loaded but checks nothing.
+pub fn load_fixtures(dir: &Path) -> io::Result<Vec<Fixture>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<_>>()?;
+    paths.retain(|p| p.extension().is_some_and(|e| e == "txt"));
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let answers_path = path.with_extension("json");
+            let answers = if answers_path.exists() {
+                serde_json::from_str(&read(&answers_path)?).map_err(|err| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{}: {}", answers_path.display(), err),
+                    )
+                })?
+            } else {
+                Answers::default()
+            };
+
+            Ok(Fixture {
+                name: path.file_stem().unwrap().to_string_lossy().into_owned(),
+                input: read(&path)?,
+                answers,
+            })
+        })
+        .collect()
+}
+
+pub enum Outcome {
+    Pass,
+    Fail { expected: Answer, actual: Answer },
+    Error(SolveError),
+    Panic,
+    TimedOut(Duration),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pass => write!(f, "ok"),
+            Self::Fail { expected, actual } => {
+                write!(f, "FAILED: expected {}, got {}", expected, actual)
+            }
+            Self::Error(err) => write!(f, "FAILED: {}", err),
+            Self::Panic => write!(f, "FAILED: panicked"),
+            Self::TimedOut(timeout) => write!(
+                f,
+                "FAILED: timed out after {} seconds",
+                timeout.as_secs_f64()
+            ),
+        }
+    }
+}
+
+pub struct CaseResult {
+    pub day: i32,
+    // None when the fixture failed to parse, which fails every part it has
+    // answers for.
+    pub part: Option<Part>,
+    pub fixture: String,
+    pub outcome: Outcome,
+}
+
+impl CaseResult {
+    pub fn passed(&self) -> bool {
+        matches!(self.outcome, Outcome::Pass)
+    }
+}
+
+fn check_fixture(
+    solver: &Arc<dyn Puzzle>,
+    fixture: &Fixture,
+    timeout: Option<Duration>,
+) -> Vec<CaseResult> {
+    let result = |part, outcome| CaseResult {
+        day: solver.day(),
+        part,
+        fixture: fixture.name.clone(),
+        outcome,
+    };
+    let parts: Vec<(Part, &Answer)> = [Part::Part1, Part::Part2]
+        .into_iter()
+        .filter_map(|part| fixture.answers.get(part).map(|a| (part, a)))
+        .collect();
+
+    if parts.is_empty() {
+        return Vec::new();
+    }
+
+    let parsed = match panic::catch_unwind(AssertUnwindSafe(|| {
+        aoc::parse(solver.as_ref(), &fixture.input)
+    })) {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(err)) => return vec![result(None, Outcome::Error(err))],
+        Err(_) => return vec![result(None, Outcome::Panic)],
+    };
+
+    parts
+        .into_iter()
+        .map(|(part, expected)| {
+            let outcome = match aoc::run_with_timeout(part, solver, &parsed, timeout) {
+                Ok(solution) if solution.answer == *expected => Outcome::Pass,
+                Ok(solution) => Outcome::Fail {
+                    expected: expected.clone(),
+                    actual: solution.answer,
+                },
+                Err(RunError::Failed(err)) => Outcome::Error(err),
+                Err(RunError::Panicked) => Outcome::Panic,
+                Err(RunError::TimedOut(timeout)) => Outcome::TimedOut(timeout),
+            };
+
+            result(Some(part), outcome)
+        })
+        .collect()
+}
+
+pub fn check(
+    dispatch_table: &DispatchTable,
+    year: i32,
+    days: RangeInclusive<i32>,
+    timeout: Option<Duration>,
+) -> io::Result<Vec<CaseResult>> {
+    let mut results = Vec::new();
+
+    for (day, solver) in dispatch_table.iter().filter(|(day, _)| days.contains(day)) {
+        for fixture in load_fixtures(&fixture_dir(year, *day))? {
+            results.extend(check_fixture(solver, &fixture, timeout));
+        }
+    }
+
+    Ok(results)
+}
+
+// Checks cached puzzle inputs against the correct answers in the ledger.
+// Days without a cached input are skipped.
+pub fn check_inputs(
+    dispatch_table: &DispatchTable,
+    year: i32,
+    days: RangeInclusive<i32>,
+    ledger: &Ledger,
+    timeout: Option<Duration>,
+) -> Result<Vec<CaseResult>, InputError> {
+    let mut results = Vec::new();
+
+    for (day, solver) in dispatch_table.iter().filter(|(day, _)| days.contains(day)) {
+        let input = match aoc::read_cached_input_for_day(year, *day) {
+            Ok(input) => input,
+            Err(InputError::NotCached(_)) => continue,
+            Err(err) => return Err(err),
+        };
+        let correct = |part| ledger.entry(*day, part).and_then(|e| e.correct.clone());
+        let fixture = Fixture {
+            name: "input".to_owned(),
+            input,
+            answers: Answers {
+                part_1: correct(Part::Part1),
+                part_2: correct(Part::Part2),
+            },
+        };
+
+        results.extend(check_fixture(solver, &fixture, timeout));
+    }
+
+    Ok(results)
+}
+
+pub fn print_results(results: &[CaseResult]) {
+    println!("{:>3}  {:>4}  {:<12}  Result", "Day", "Part", "Fixture");
+
+    for result in results {
+        let part = result.part.map_or("-".to_owned(), |p| p.to_string());
+        println!(
+            "{:>3}  {:>4}  {:<12}  {}",
+            result.day, part, result.fixture, result.outcome
+        );
+    }
+
+    let passed = results.iter().filter(|r| r.passed()).count();
+    println!("{} passed, {} failed", passed, results.len() - passed);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::RangeInclusive;
+
+    use super::{check, check_inputs, extract_examples, Answers, CaseResult, Example};
+    use crate::{
+        aoc::Answer,
+        ledger::{self, Ledger},
+        registry,
+    };
+
+    fn describe(year: i32, result: &CaseResult) -> String {
+        let part = result
+            .part
+            .map_or("parsing".to_owned(), |p| format!("part {}", p));
+        format!(
+            "{} day {} {} {}: {}",
+            year, result.day, part, result.fixture, result.outcome
+        )
+    }
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<p>In this example, the values are <code>12</code> and <code>38</code>.
+Adding these together produces <code><em>50</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54601</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>For example:</p>
+<pre><code>two1nine
+<em>eight</em>wo&lt;three
+</code></pre>
+<p>Adding these together produces <em><code>29</code></em>.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn extracts_examples() {
+        assert_eq!(
+            extract_examples(PAGE),
+            vec![
+                Example {
+                    input: "1abc2\npqr3stu8vwx\n".to_owned(),
+                    answers: Answers {
+                        part_1: Some(Answer::from(50)),
+                        part_2: None,
+                    },
+                },
+                Example {
+                    input: "two1nine\neightwo<three\n".to_owned(),
+                    answers: Answers {
+                        part_1: None,
+                        part_2: Some(Answer::from(29)),
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reuses_first_example_for_part_2() {
+        let page = PAGE.replace(
+            "<pre><code>two1nine\n<em>eight</em>wo&lt;three\n</code></pre>",
+            "",
+        );
+        let examples = extract_examples(&page);
+
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].answers.part_2, Some(Answer::from(29)));
+    }
+
+    // Runs every registered solver against the examples under fixtures/.
+    #[test]
+    fn examples_pass() {
+        let mut failures = Vec::new();
+
+        for (year, dispatch_table) in registry() {
+            for result in check(&dispatch_table, year, 1..=25, None).unwrap() {
+                if !result.passed() {
+                    failures.push(describe(year, &result));
+                }
+            }
+        }
+
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn skips_empty_day_ranges() {
+        for (year, dispatch_table) in registry() {
+            assert!(
+                check(&dispatch_table, year, RangeInclusive::new(5, 3), None)
+                    .unwrap()
+                    .is_empty()
+            );
+        }
+    }
+
+    // Real inputs are personal and not checked in, so this only runs on
+    // request: cargo test -- --ignored
+    #[test]
+    #[ignore = "needs cached inputs and a ledger"]
+    fn inputs_match_ledger() {
+        let mut failures = Vec::new();
+
+        for (year, dispatch_table) in registry() {
+            let ledger = Ledger::load(&ledger::ledger_path(year)).unwrap();
+
+            for result in check_inputs(&dispatch_table, year, 1..=25, &ledger, None).unwrap() {
+                if !result.passed() {
+                    failures.push(describe(year, &result));
+                }
+            }
+        }
+
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
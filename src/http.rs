@@ -0,0 +1,183 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::Lazy;
+use reqwest::{redirect::Policy, Client, RequestBuilder, StatusCode};
+use tokio::sync::Mutex;
+
+use crate::aoc::USER_AGENT;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+// Requests are spaced out so a loop over every day doesn't hammer the site.
+const MIN_INTERVAL: Duration = Duration::from_secs(1);
+const RETRIES: u32 = 3;
+const BACKOFF: Duration = Duration::from_secs(2);
+
+// Redirects are not followed: the site only redirects requests that need a
+// login, and following them would return the login page as if it were data.
+static CLIENT: Lazy<Client> = Lazy::new(|| {
+    Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(TIMEOUT)
+        .connect_timeout(CONNECT_TIMEOUT)
+        .redirect(Policy::none())
+        .build()
+        .expect("failed to build HTTP client")
+});
+static LAST_REQUEST: Lazy<Mutex<Option<Instant>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug)]
+pub enum HttpError {
+    Request(reqwest::Error),
+    NotLoggedIn,
+    NotUnlocked,
+    Status(StatusCode),
+}
+
+impl Display for HttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Request(err) => write!(f, "{}", err),
+            Self::NotLoggedIn => write!(
+                f,
+                "the site asked to log in; SESSION_ID is missing, invalid or expired"
+            ),
+            Self::NotUnlocked => write!(f, "the puzzle has not unlocked yet"),
+            Self::Status(status) => write!(f, "the server responded with {}", status),
+        }
+    }
+}
+
+impl Error for HttpError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Request(err) => Some(err),
+            Self::NotLoggedIn | Self::NotUnlocked | Self::Status(_) => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for HttpError {
+    fn from(value: reqwest::Error) -> Self {
+        Self::Request(value)
+    }
+}
+
+fn check_response(status: StatusCode, body: String) -> Result<String, HttpError> {
+    if status.is_success() {
+        Ok(body)
+    } else if status.is_redirection() || body.contains("Please log in") {
+        Err(HttpError::NotLoggedIn)
+    } else if status == StatusCode::NOT_FOUND && body.contains("before it unlocks") {
+        Err(HttpError::NotUnlocked)
+    } else {
+        Err(HttpError::Status(status))
+    }
+}
+
+fn is_transient(err: &HttpError) -> bool {
+    match err {
+        HttpError::Request(err) => err.is_timeout() || err.is_connect(),
+        HttpError::Status(status) => {
+            status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+        }
+        HttpError::NotLoggedIn | HttpError::NotUnlocked => false,
+    }
+}
+
+async fn throttle() {
+    let mut last = LAST_REQUEST.lock().await;
+
+    if let Some(wait) = last.and_then(|last| MIN_INTERVAL.checked_sub(last.elapsed())) {
+        tokio::time::sleep(wait).await;
+    }
+
+    *last = Some(Instant::now());
+}
+
+async fn send_once(request: RequestBuilder) -> Result<String, HttpError> {
+    throttle().await;
+    let response = request.send().await?;
+    let status = response.status();
+
+    check_response(status, response.text().await?)
+}
+
+async fn send(request: impl Fn() -> RequestBuilder, retries: u32) -> Result<String, HttpError> {
+    let mut attempt = 0;
+
+    loop {
+        match send_once(request()).await {
+            Err(err) if attempt < retries && is_transient(&err) => {
+                tokio::time::sleep(BACKOFF * 2u32.pow(attempt)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn with_session(request: RequestBuilder, session_id: Option<&str>) -> RequestBuilder {
+    match session_id {
+        Some(session_id) => request.header("Cookie", format!("session={}", session_id)),
+        None => request,
+    }
+}
+
+pub async fn get(url: &str, session_id: Option<&str>) -> Result<String, HttpError> {
+    send(|| with_session(CLIENT.get(url), session_id), RETRIES).await
+}
+
+// Posts are never retried, since the first attempt may have gone through.
+pub async fn post_form(
+    url: &str,
+    session_id: Option<&str>,
+    form: &[(&str, &str)],
+) -> Result<String, HttpError> {
+    send(|| with_session(CLIENT.post(url), session_id).form(form), 0).await
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+
+    use super::{check_response, is_transient, HttpError};
+
+    #[test]
+    fn classifies_responses() {
+        assert_eq!(
+            check_response(StatusCode::OK, "1abc2\n".to_owned()).unwrap(),
+            "1abc2\n"
+        );
+        assert!(matches!(
+            check_response(
+                StatusCode::BAD_REQUEST,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+                    .to_owned()
+            ),
+            Err(HttpError::NotLoggedIn)
+        ));
+        assert!(matches!(
+            check_response(StatusCode::FOUND, String::new()),
+            Err(HttpError::NotLoggedIn)
+        ));
+        assert!(matches!(
+            check_response(
+                StatusCode::NOT_FOUND,
+                "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time.\n"
+                    .to_owned()
+            ),
+            Err(HttpError::NotUnlocked)
+        ));
+
+        let err = check_response(StatusCode::SERVICE_UNAVAILABLE, String::new()).unwrap_err();
+        assert!(is_transient(&err));
+        let err = check_response(StatusCode::NOT_FOUND, "404 Not Found".to_owned()).unwrap_err();
+        assert!(matches!(err, HttpError::Status(StatusCode::NOT_FOUND)));
+        assert!(!is_transient(&err));
+    }
+}
@@ -0,0 +1,356 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use serde::Deserialize;
+
+use crate::{
+    aoc::{self, MissingSessionId, Part, BASE_URL},
+    http::{self, HttpError},
+};
+
+// The site asks for private leaderboards to be fetched at most once every
+// 15 minutes.
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub completion_day_level: BTreeMap<i32, BTreeMap<u8, Star>>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    pub fn star(&self, day: i32, part: Part) -> Option<i64> {
+        self.completion_day_level
+            .get(&day)
+            .and_then(|d| d.get(&u8::from(part)))
+            .map(|s| s.get_star_ts)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: BTreeMap<String, Member>,
+}
+
+impl Leaderboard {
+    // Highest local score first, the same order as on the site.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by(|a, b| b.local_score.cmp(&a.local_score).then(a.id.cmp(&b.id)));
+        members
+    }
+
+    pub fn last_day(&self) -> i32 {
+        self.members
+            .values()
+            .flat_map(|m| m.completion_day_level.keys())
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Debug)]
+pub enum LeaderboardError {
+    Session(MissingSessionId),
+    NotCached(PathBuf),
+    Read(PathBuf, io::Error),
+    Write(PathBuf, io::Error),
+    Parse(serde_json::Error),
+    Http(HttpError),
+}
+
+impl Display for LeaderboardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Session(err) => {
+                write!(f, "{} and is required to fetch a private leaderboard", err)
+            }
+            Self::NotCached(path) => write!(
+                f,
+                "{} does not exist and downloading is disabled in offline mode",
+                path.display()
+            ),
+            Self::Read(path, err) => write!(f, "Failed to read {}: {}", path.display(), err),
+            Self::Write(path, err) => write!(f, "Failed to write {}: {}", path.display(), err),
+            Self::Parse(err) => write!(f, "Failed to parse leaderboard: {}", err),
+            Self::Http(err) => write!(f, "Failed to download leaderboard: {}", err),
+        }
+    }
+}
+
+impl Error for LeaderboardError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Session(err) => Some(err),
+            Self::NotCached(_) => None,
+            Self::Read(_, err) | Self::Write(_, err) => Some(err),
+            Self::Parse(err) => Some(err),
+            Self::Http(err) => Some(err),
+        }
+    }
+}
+
+impl From<HttpError> for LeaderboardError {
+    fn from(value: HttpError) -> Self {
+        Self::Http(value)
+    }
+}
+
+impl From<MissingSessionId> for LeaderboardError {
+    fn from(value: MissingSessionId) -> Self {
+        Self::Session(value)
+    }
+}
+
+fn leaderboard_cache_path(year: i32, id: u64) -> PathBuf {
+    PathBuf::from(format!("leaderboards/{}/{}.json", year, id))
+}
+
+pub fn parse(json: &str) -> Result<Leaderboard, LeaderboardError> {
+    serde_json::from_str(json).map_err(LeaderboardError::Parse)
+}
+
+pub fn read_file(path: &Path) -> Result<Leaderboard, LeaderboardError> {
+    parse(&fs::read_to_string(path).map_err(|err| LeaderboardError::Read(path.to_owned(), err))?)
+}
+
+async fn download_leaderboard(year: i32, id: u64) -> Result<String, LeaderboardError> {
+    let session_id = aoc::require_session_id()?;
+    let url = format!("{}/{}/leaderboard/private/view/{}.json", BASE_URL, year, id);
+
+    Ok(http::get(&url, Some(&session_id)).await?)
+}
+
+fn cache_age(path: &Path) -> Option<Duration> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    // A timestamp in the future counts as brand new.
+    Some(
+        SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default(),
+    )
+}
+
+// Returns the leaderboard and the age of the cached copy it came from, if
+// it was not downloaded just now. Offline, any cached copy is used.
+pub async fn get_leaderboard(
+    year: i32,
+    id: u64,
+    offline: bool,
+) -> Result<(Leaderboard, Option<Duration>), LeaderboardError> {
+    let path = leaderboard_cache_path(year, id);
+    let age = cache_age(&path);
+
+    if let Some(age) = age.filter(|age| offline || *age < REFRESH_INTERVAL) {
+        return Ok((read_file(&path)?, Some(age)));
+    }
+
+    if offline {
+        return Err(LeaderboardError::NotCached(path));
+    }
+
+    let json = download_leaderboard(year, id).await?;
+    // Parse before caching, so a bad response doesn't hold up the next fetch.
+    let leaderboard = parse(&json)?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|err| LeaderboardError::Write(dir.to_owned(), err))?;
+    }
+    fs::write(&path, json).map_err(|err| LeaderboardError::Write(path, err))?;
+
+    Ok((leaderboard, None))
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146097 + doe - 719468
+}
+
+// Puzzles unlock at midnight US Eastern time, which is UTC-5 in December.
+pub fn unlock_ts(year: i32, day: i32) -> i64 {
+    days_from_civil(year as i64, 12, day as i64) * 86400 + 5 * 3600
+}
+
+fn format_duration(secs: i64) -> String {
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+pub fn render(leaderboard: &Leaderboard, day: Option<i32>) -> String {
+    let year: i32 = leaderboard.event.parse().unwrap_or_default();
+    let members = leaderboard.ranked();
+    let last_day = leaderboard.last_day();
+    let width = members
+        .iter()
+        .map(|m| m.display_name().len())
+        .max()
+        .unwrap_or(0)
+        .max("Name".len());
+
+    let mut lines = Vec::new();
+    let days: String = (1..=last_day).map(|d| format!(" {:>2}", d)).collect();
+    lines.push(format!(
+        "{:>3}  {:>5}  {:>5}  {:<width$} {}",
+        "#", "Score", "Stars", "Name", days
+    ));
+
+    for (rank, member) in (1..).zip(&members) {
+        let stars: String = (1..=last_day)
+            .map(
+                |d| match (member.star(d, Part::Part1), member.star(d, Part::Part2)) {
+                    (Some(_), Some(_)) => "  *",
+                    (Some(_), None) => "  +",
+                    _ => "  .",
+                },
+            )
+            .collect();
+        lines.push(format!(
+            "{:>3}  {:>5}  {:>5}  {:<width$} {}",
+            rank,
+            member.local_score,
+            member.stars,
+            member.display_name(),
+            stars
+        ));
+    }
+
+    let days = match day {
+        Some(day) => day..=day,
+        None => 1..=last_day,
+    };
+
+    for day in days {
+        let unlock = unlock_ts(year, day);
+        let mut finishers: Vec<(&Member, i64, Option<i64>)> = members
+            .iter()
+            .filter_map(|m| {
+                let part_1 = m.star(day, Part::Part1)?;
+                Some((*m, part_1, m.star(day, Part::Part2)))
+            })
+            .collect();
+
+        if finishers.is_empty() {
+            continue;
+        }
+
+        // Whoever finished both parts first leads, then by part 1.
+        finishers.sort_by_key(|(_, part_1, part_2)| (part_2.unwrap_or(i64::MAX), *part_1));
+
+        lines.push(String::new());
+        lines.push(format!(
+            "{:<width$}  {:>9}  {:>9}  {:>9}",
+            format!("Day {}", day),
+            "Part 1",
+            "Part 2",
+            "Delta"
+        ));
+
+        for (member, part_1, part_2) in finishers {
+            let (part_2, delta) = match part_2 {
+                Some(part_2) => (
+                    format_duration(part_2 - unlock),
+                    format_duration(part_2 - part_1),
+                ),
+                None => ("-".to_owned(), "-".to_owned()),
+            };
+            lines.push(format!(
+                "{:<width$}  {:>9}  {:>9}  {:>9}",
+                member.display_name(),
+                format_duration(part_1 - unlock),
+                part_2,
+                delta
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, render, unlock_ts};
+
+    // 2023-12-01T05:00:00Z
+    const DAY_1: i64 = 1701406800;
+
+    fn leaderboard_json() -> String {
+        format!(
+            r#"{{
+  "owner_id": 1,
+  "event": "2023",
+  "members": {{
+    "1": {{
+      "id": 1, "name": "Alice", "stars": 3, "local_score": 5, "global_score": 0,
+      "last_star_ts": {p2},
+      "completion_day_level": {{
+        "1": {{"1": {{"get_star_ts": {a1}, "star_index": 1}}, "2": {{"get_star_ts": {p2}, "star_index": 3}}}},
+        "2": {{"1": {{"get_star_ts": {d2}, "star_index": 5}}}}
+      }}
+    }},
+    "2": {{
+      "id": 2, "name": null, "stars": 1, "local_score": 2, "global_score": 0,
+      "last_star_ts": {b1},
+      "completion_day_level": {{
+        "1": {{"1": {{"get_star_ts": {b1}, "star_index": 2}}}}
+      }}
+    }}
+  }}
+}}"#,
+            a1 = DAY_1 + 300,
+            p2 = DAY_1 + 3900,
+            b1 = DAY_1 + 600,
+            d2 = DAY_1 + 86400 + 59,
+        )
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        assert_eq!(unlock_ts(2023, 1), DAY_1);
+        assert_eq!(unlock_ts(2023, 25) - unlock_ts(2023, 1), 24 * 86400);
+    }
+
+    #[test]
+    fn renders_leaderboard() {
+        let leaderboard = parse(&leaderboard_json()).unwrap();
+
+        assert_eq!(
+            render(&leaderboard, None),
+            "  #  Score  Stars  Name                  1  2
+  1      5      3  Alice                 *  +
+  2      2      1  (anonymous user #2)   +  .
+
+Day 1                   Part 1     Part 2      Delta
+Alice                 00:05:00   01:05:00   01:00:00
+(anonymous user #2)   00:10:00          -          -
+
+Day 2                   Part 1     Part 2      Delta
+Alice                 00:00:59          -          -"
+        );
+    }
+}
@@ -0,0 +1,318 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    aoc::{Answer, Part},
+    submit::{Hint, SubmissionResult},
+};
+
+pub fn ledger_path(year: i32) -> PathBuf {
+    PathBuf::from(format!("answers/{}.json", year))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Guess {
+    pub answer: Answer,
+    pub hint: Option<Hint>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Entry {
+    pub day: i32,
+    pub part: Part,
+    pub correct: Option<Answer>,
+    pub rejected: Vec<Guess>,
+}
+
+impl Entry {
+    fn new(day: i32, part: Part) -> Self {
+        Entry {
+            day,
+            part,
+            correct: None,
+            rejected: Vec::new(),
+        }
+    }
+
+    fn lowest_too_high(&self) -> Option<i128> {
+        self.rejected
+            .iter()
+            .filter(|g| g.hint == Some(Hint::TooHigh))
+            .filter_map(|g| g.answer.as_integer())
+            .min()
+    }
+
+    fn highest_too_low(&self) -> Option<i128> {
+        self.rejected
+            .iter()
+            .filter(|g| g.hint == Some(Hint::TooLow))
+            .filter_map(|g| g.answer.as_integer())
+            .max()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Unknown,
+    Correct,
+    Incorrect(Answer),
+    Rejected(Option<Hint>),
+    OutOfBounds(Hint, Answer),
+}
+
+impl Verdict {
+    pub fn summary(&self) -> &'static str {
+        match self {
+            Self::Unknown => "?",
+            Self::Correct => "correct",
+            Self::Incorrect(_) => "WRONG",
+            Self::Rejected(_) | Self::OutOfBounds(_, _) => "rejected",
+        }
+    }
+
+    // A stable name for machine-readable output.
+    pub fn status(&self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Correct => "correct",
+            Self::Incorrect(_) => "incorrect",
+            Self::Rejected(_) | Self::OutOfBounds(_, _) => "rejected",
+        }
+    }
+
+    pub fn is_known_wrong(&self) -> bool {
+        matches!(
+            self,
+            Self::Incorrect(_) | Self::Rejected(_) | Self::OutOfBounds(_, _)
+        )
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unknown => write!(f, "No known answer to compare against"),
+            Self::Correct => write!(f, "Matches the known correct answer"),
+            Self::Incorrect(expected) => {
+                write!(f, "Does not match the known correct answer {}", expected)
+            }
+            Self::Rejected(None) => write!(f, "Was already rejected"),
+            Self::Rejected(Some(Hint::TooHigh)) => write!(f, "Was already rejected as too high"),
+            Self::Rejected(Some(Hint::TooLow)) => write!(f, "Was already rejected as too low"),
+            Self::OutOfBounds(Hint::TooHigh, bound) => {
+                write!(f, "Is too high, {} was already rejected as too high", bound)
+            }
+            Self::OutOfBounds(Hint::TooLow, bound) => {
+                write!(f, "Is too low, {} was already rejected as too low", bound)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum LedgerError {
+    Read(PathBuf, io::Error),
+    Write(PathBuf, io::Error),
+    Parse(PathBuf, serde_json::Error),
+}
+
+impl Display for LedgerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read(path, err) => write!(f, "Failed to read {}: {}", path.display(), err),
+            Self::Write(path, err) => write!(f, "Failed to write {}: {}", path.display(), err),
+            Self::Parse(path, err) => write!(f, "Failed to parse {}: {}", path.display(), err),
+        }
+    }
+}
+
+impl Error for LedgerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Read(_, err) | Self::Write(_, err) => Some(err),
+            Self::Parse(_, err) => Some(err),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Ledger {
+    entries: Vec<Entry>,
+}
+
+impl Ledger {
+    pub fn load(path: &Path) -> Result<Self, LedgerError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let json =
+            fs::read_to_string(path).map_err(|err| LedgerError::Read(path.to_owned(), err))?;
+        serde_json::from_str(&json).map_err(|err| LedgerError::Parse(path.to_owned(), err))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), LedgerError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|err| LedgerError::Write(dir.to_owned(), err))?;
+        }
+
+        let json = serde_json::to_string_pretty(self).unwrap();
+        fs::write(path, json).map_err(|err| LedgerError::Write(path.to_owned(), err))
+    }
+
+    pub fn entry(&self, day: i32, part: Part) -> Option<&Entry> {
+        self.entries.iter().find(|e| e.day == day && e.part == part)
+    }
+
+    pub fn is_solved(&self, day: i32, part: Part) -> bool {
+        self.entry(day, part).is_some_and(|e| e.correct.is_some())
+    }
+
+    fn entry_mut(&mut self, day: i32, part: Part) -> &mut Entry {
+        let idx = match self
+            .entries
+            .iter()
+            .position(|e| e.day == day && e.part == part)
+        {
+            Some(idx) => idx,
+            None => {
+                self.entries.push(Entry::new(day, part));
+                self.entries.sort_by_key(|e| (e.day, e.part));
+                self.entries
+                    .iter()
+                    .position(|e| e.day == day && e.part == part)
+                    .unwrap()
+            }
+        };
+
+        &mut self.entries[idx]
+    }
+
+    pub fn check(&self, day: i32, part: Part, answer: &Answer) -> Verdict {
+        let Some(entry) = self.entry(day, part) else {
+            return Verdict::Unknown;
+        };
+
+        if let Some(correct) = &entry.correct {
+            return if correct == answer {
+                Verdict::Correct
+            } else {
+                Verdict::Incorrect(correct.clone())
+            };
+        }
+
+        if let Some(guess) = entry.rejected.iter().find(|g| g.answer == *answer) {
+            return Verdict::Rejected(guess.hint);
+        }
+
+        if let Some(answer) = answer.as_integer() {
+            if let Some(bound) = entry.lowest_too_high().filter(|b| answer > *b) {
+                return Verdict::OutOfBounds(Hint::TooHigh, bound.into());
+            }
+
+            if let Some(bound) = entry.highest_too_low().filter(|b| answer < *b) {
+                return Verdict::OutOfBounds(Hint::TooLow, bound.into());
+            }
+        }
+
+        Verdict::Unknown
+    }
+
+    pub fn record(&mut self, day: i32, part: Part, answer: &Answer, result: &SubmissionResult) {
+        match result {
+            SubmissionResult::Correct => {
+                self.entry_mut(day, part).correct = Some(answer.clone());
+            }
+            SubmissionResult::Incorrect(hint) => {
+                self.entry_mut(day, part).rejected.push(Guess {
+                    answer: answer.clone(),
+                    hint: *hint,
+                });
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Ledger, Verdict};
+    use crate::{
+        aoc::{Answer, Part},
+        submit::{Hint, SubmissionResult},
+    };
+
+    #[test]
+    fn checks_against_rejected_guesses() {
+        let mut ledger = Ledger::default();
+        ledger.record(
+            5,
+            Part::Part1,
+            &Answer::from(100),
+            &SubmissionResult::Incorrect(Some(Hint::TooHigh)),
+        );
+        ledger.record(
+            5,
+            Part::Part1,
+            &Answer::from(10),
+            &SubmissionResult::Incorrect(Some(Hint::TooLow)),
+        );
+        ledger.record(
+            5,
+            Part::Part1,
+            &Answer::from(50),
+            &SubmissionResult::Incorrect(None),
+        );
+
+        assert_eq!(
+            ledger.check(5, Part::Part1, &Answer::from(50)),
+            Verdict::Rejected(None)
+        );
+        assert_eq!(
+            ledger.check(5, Part::Part1, &Answer::from(100)),
+            Verdict::Rejected(Some(Hint::TooHigh))
+        );
+        assert_eq!(
+            ledger.check(5, Part::Part1, &Answer::from(150)),
+            Verdict::OutOfBounds(Hint::TooHigh, Answer::from(100))
+        );
+        assert_eq!(
+            ledger.check(5, Part::Part1, &Answer::from(5)),
+            Verdict::OutOfBounds(Hint::TooLow, Answer::from(10))
+        );
+        assert_eq!(
+            ledger.check(5, Part::Part1, &Answer::from(42)),
+            Verdict::Unknown
+        );
+        assert_eq!(
+            ledger.check(5, Part::Part2, &Answer::from(5)),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn checks_against_correct_answer() {
+        let mut ledger = Ledger::default();
+        ledger.record(
+            5,
+            Part::Part2,
+            &Answer::from(46),
+            &SubmissionResult::Correct,
+        );
+
+        assert_eq!(
+            ledger.check(5, Part::Part2, &Answer::from(46)),
+            Verdict::Correct
+        );
+        assert_eq!(
+            ledger.check(5, Part::Part2, &Answer::from(47)),
+            Verdict::Incorrect(Answer::from(46))
+        );
+    }
+}
@@ -0,0 +1,57 @@
+pub mod aoc;
+pub mod bench;
+pub mod fixtures;
+pub mod http;
+pub mod leaderboard;
+pub mod ledger;
+pub mod pool;
+pub mod puzzle;
+pub mod scaffold;
+pub mod submit;
+pub mod watch;
+
+use std::{collections::BTreeMap, sync::Arc};
+
+pub use aoc::{Answer, Part, Puzzle, SolveError, Solver};
+
+// Solvers for one year, by day.
+pub type DispatchTable = BTreeMap<i32, Arc<dyn Puzzle>>;
+pub type Registry = BTreeMap<i32, DispatchTable>;
+
+// Declares each day's module and registers its solver under the year and day
+// it reports, so adding a day only takes a line in the days! list below.
+macro_rules! days {
+    ($($module:ident::$solver:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        pub fn registry() -> Registry {
+            let mut registry = Registry::new();
+
+            $(
+                let solver: Arc<dyn Puzzle> = Arc::new($module::$solver::new());
+                let (year, day) = (solver.year(), solver.day());
+                assert!(
+                    registry.entry(year).or_default().insert(day, solver).is_none(),
+                    "Day {} of {} is registered twice",
+                    day,
+                    year
+                );
+            )*
+
+            registry
+        }
+    };
+}
+
+days! {
+    day1::Day1,
+    day2::Day2,
+    day3::Day3,
+    day4::Day4,
+    day5::Day5,
+    day6::Day6,
+    day7::Day7,
+    day8::Day8,
+    day9::Day9,
+    day10::Day10,
+}
@@ -1,71 +1,1041 @@
-mod aoc;
-mod day1;
-mod day10;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-use aoc::Solver;
-use clap::Parser;
-use day1::Day1;
-use day10::Day10;
-use day2::Day2;
-use day3::Day3;
-use day4::Day4;
-use day5::Day5;
-use day6::Day6;
-use day7::Day7;
-use day8::Day8;
-use day9::Day9;
-use std::collections::HashMap;
+use advent_of_code_2023::{
+    aoc::{self, Answer, InputSource, Part, Puzzle, Record, Solution},
+    bench,
+    fixtures::{self, Written},
+    leaderboard,
+    ledger::{self, Ledger, Verdict},
+    pool, puzzle, registry, scaffold, submit,
+    watch::{self, Watcher},
+    DispatchTable,
+};
+use clap::{ArgGroup, Parser, Subcommand, ValueEnum};
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
+// Selection is shared with subcommands that have no --list, so each command
+// says which arguments pick the days to run.
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+#[command(group(ArgGroup::new("days").args(["day", "all", "list"]).required(true)))]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    selection: Selection,
+    #[command(flatten)]
+    download: Download,
+    #[arg(long, short, value_name = "PATH", conflicts_with_all = ["refresh", "offline"])]
+    input: Option<PathBuf>,
+    /// List the days that have a solver
+    #[arg(long, exclusive = true)]
+    list: bool,
+    /// Solve up to N days and parts at once when running several days; 0 uses every core
+    #[arg(long, short, value_name = "N", default_value_t = 1)]
+    jobs: usize,
+    /// How to print the answers; json and csv also include timings and ledger verdicts
+    #[arg(long, value_enum, default_value_t = Format::Plain)]
+    format: Format,
+    /// Give up on a part after this many seconds and move on to the next
+    #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+    /// Puzzle year; defaults to the latest year that has solvers
+    #[arg(long, short, global = true)]
+    year: Option<i32>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Plain,
+    Json,
+    Csv,
+}
+
+#[derive(clap::Args)]
+struct Selection {
     #[arg(long)]
     part_1: bool,
     #[arg(long)]
     part_2: bool,
-    #[arg(long, short)]
+    /// A single day, or a range of days such as 3..7 or 3..=7
+    #[arg(long, short, value_parser = parse_days)]
+    day: Option<RangeInclusive<i32>>,
+    /// Run both parts of every implemented day
+    #[arg(long, conflicts_with = "day")]
+    all: bool,
+}
+
+impl Selection {
+    fn days(&self) -> RangeInclusive<i32> {
+        // --all is the only way to leave out --day and covers every puzzle day.
+        self.day.clone().unwrap_or(1..=25)
+    }
+
+    fn parts(&self) -> Vec<Part> {
+        parts(self.part_1, self.part_2)
+    }
+}
+
+fn parts(part_1: bool, part_2: bool) -> Vec<Part> {
+    match (part_1, part_2) {
+        (true, false) => vec![Part::Part1],
+        (false, true) => vec![Part::Part2],
+        _ => vec![Part::Part1, Part::Part2],
+    }
+}
+
+#[derive(clap::Args)]
+struct Download {
+    #[arg(long, conflicts_with = "offline")]
+    refresh: bool,
+    #[arg(long)]
+    offline: bool,
+}
+
+impl Download {
+    fn source(&self) -> InputSource {
+        if self.offline {
+            InputSource::Cached
+        } else {
+            InputSource::Puzzle {
+                refresh: self.refresh,
+            }
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    Submit {
+        #[arg(long, short)]
+        day: i32,
+        #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Answer to submit; solves the cached puzzle input if omitted
+        answer: Option<String>,
+        #[arg(long, default_value = aoc::BASE_URL)]
+        base_url: String,
+    },
+    Examples {
+        #[arg(long, short)]
+        day: i32,
+        /// Read a saved copy of the puzzle page instead of downloading it
+        #[arg(long, value_name = "PATH")]
+        html: Option<PathBuf>,
+        /// Overwrite fixtures that already exist
+        #[arg(long)]
+        force: bool,
+    },
+    Read {
+        #[arg(long, short)]
+        day: i32,
+        #[command(flatten)]
+        download: Download,
+        /// Render a saved copy of the puzzle page instead
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["refresh", "offline"])]
+        html: Option<PathBuf>,
+    },
+    New {
+        #[arg(long, short)]
+        day: i32,
+        #[command(flatten)]
+        download: Download,
+    },
+    /// Re-run a day whenever its input, examples or source change
+    Watch {
+        #[arg(long, short)]
+        day: i32,
+        #[arg(long)]
+        part_1: bool,
+        #[arg(long)]
+        part_2: bool,
+        /// How often to check for changes, in milliseconds
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+        /// Give up on a part after this many seconds and wait for the next change
+        #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+        #[command(flatten)]
+        download: Download,
+    },
+    Leaderboard {
+        /// Id of the private leaderboard, as in its URL
+        #[arg(required_unless_present = "file")]
+        id: Option<u64>,
+        /// Only show completion times for this day
+        #[arg(long, short)]
+        day: Option<i32>,
+        /// Use the cached copy however old it is
+        #[arg(long)]
+        offline: bool,
+        /// Read a saved copy of the leaderboard JSON instead
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["id", "offline"])]
+        file: Option<PathBuf>,
+    },
+    Test {
+        /// A single day, or a range of days; defaults to every day
+        #[arg(long, short, value_parser = parse_days)]
+        day: Option<RangeInclusive<i32>>,
+        /// Check cached puzzle inputs against the ledger instead of the examples
+        #[arg(long)]
+        real: bool,
+        /// Fail a part after this many seconds and move on to the next
+        #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+    },
+    #[command(group(ArgGroup::new("days").args(["day", "all"]).required(true)))]
+    Bench {
+        #[command(flatten)]
+        selection: Selection,
+        #[command(flatten)]
+        download: Download,
+        #[arg(long, short = 'n', default_value_t = 100)]
+        iterations: usize,
+        #[arg(long, default_value_t = 10)]
+        warmup: usize,
+        /// Also write the results as JSON to PATH, or stdout for -
+        #[arg(long, value_name = "PATH")]
+        json: Option<PathBuf>,
+        /// Append the results to the benchmark history for the current commit
+        #[arg(long)]
+        save: bool,
+        /// Compare the results against the benchmark history
+        #[arg(long)]
+        compare: bool,
+        /// Commit to compare against; defaults to the latest other commit
+        #[arg(long, requires = "compare")]
+        baseline: Option<String>,
+        /// Percentage slowdown of the median that counts as a regression
+        #[arg(long, default_value_t = 10.0, requires = "compare")]
+        threshold: f64,
+    },
+}
+
+fn parse_days(s: &str) -> Result<RangeInclusive<i32>, String> {
+    let parse = |n: &str| {
+        n.trim()
+            .parse::<i32>()
+            .map_err(|_| format!("{} is not a day number", n))
+    };
+
+    let days = if let Some((start, end)) = s.split_once("..=") {
+        parse(start)?..=parse(end)?
+    } else if let Some((start, end)) = s.split_once("..") {
+        parse(start)?..=(parse(end)? - 1)
+    } else {
+        let day = parse(s)?;
+        day..=day
+    };
+
+    if days.is_empty() {
+        return Err(format!("{} does not contain any days", s));
+    }
+
+    Ok(days)
+}
+
+fn parse_timeout(s: &str) -> Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .filter(|secs| *secs > 0.0)
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or_else(|| format!("{} is not a positive number of seconds", s))
+}
+
+fn or_exit<T, E: Display>(result: Result<T, E>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn get_inputs<'a>(
+    dispatch_table: &'a DispatchTable,
+    year: i32,
+    days: &RangeInclusive<i32>,
+    source: &InputSource,
+) -> Vec<(&'a Arc<dyn Puzzle>, String)> {
+    let mut inputs = Vec::new();
+
+    for (day, solver) in dispatch_table.iter().filter(|(day, _)| days.contains(day)) {
+        match aoc::get_input(year, *day, source).await {
+            Ok(input) => inputs.push((solver, input)),
+            Err(err) => eprintln!("Day {}: {}", day, err),
+        }
+    }
+
+    inputs
+}
+
+async fn submit(
+    dispatch_table: &DispatchTable,
+    ledger: &mut Ledger,
+    year: i32,
     day: i32,
+    part: Part,
+    answer: Option<Answer>,
+    base_url: &str,
+) {
+    let session_id = or_exit(aoc::require_session_id().map_err(submit::SubmitError::from));
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let Some(solver) = dispatch_table.get(&day) else {
+                eprintln!("Day {} of {} is not yet implemented.", day, year);
+                return;
+            };
+
+            let source = InputSource::Puzzle { refresh: false };
+            let input = or_exit(aoc::get_input(year, day, &source).await);
+            let Some(answer) = aoc::parse_or_report(solver.as_ref(), &input)
+                .and_then(|parsed| aoc::solve(part, solver, &input, &parsed, ledger, None))
+            else {
+                std::process::exit(1);
+            };
+            answer
+        }
+    };
+
+    match ledger.check(day, part, &answer) {
+        Verdict::Correct => {
+            println!("{} is already known to be correct, not submitting", answer);
+            return;
+        }
+        verdict if verdict.is_known_wrong() => {
+            eprintln!("Refusing to submit {}: {}", answer, verdict);
+            std::process::exit(1);
+        }
+        _ => (),
+    }
+
+    let result =
+        or_exit(submit::submit_answer(base_url, &session_id, year, day, part, &answer).await);
+    println!("{}", result);
+
+    ledger.record(day, part, &answer, &result);
+    or_exit(ledger.save(&ledger::ledger_path(year)));
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args = Args::parse();
+struct SolveOptions {
+    jobs: usize,
+    format: Format,
+    timeout: Option<Duration>,
+}
+
+fn print_solutions(year: i32, solutions: &[Solution], ledger: &Ledger, format: Format) {
+    let records = || -> Vec<Record> {
+        solutions
+            .iter()
+            .map(|solution| Record::new(year, solution, ledger))
+            .collect()
+    };
+
+    match format {
+        Format::Plain => aoc::print_summary(solutions, ledger),
+        Format::Json => println!("{}", aoc::records_json(&records())),
+        Format::Csv => print!("{}", aoc::records_csv(&records())),
+    }
+}
+
+async fn solve(
+    dispatch_table: &DispatchTable,
+    ledger: &Ledger,
+    year: i32,
+    selection: Selection,
+    source: InputSource,
+    options: SolveOptions,
+) {
+    let SolveOptions {
+        jobs,
+        format,
+        timeout,
+    } = options;
+    let days = selection.days();
+    // The ledger only knows the answers for our own puzzle input, so answers
+    // for any other input can't be judged.
+    let unjudged = Ledger::default();
+    let ledger = match source {
+        InputSource::File(_) | InputSource::Stdin => &unjudged,
+        InputSource::Puzzle { .. } | InputSource::Cached => ledger,
+    };
+
+    if days.start() == days.end() {
+        let day = *days.start();
+        let Some(solver) = dispatch_table.get(&day) else {
+            eprintln!("Day {} of {} is not yet implemented.", day, year);
+            return;
+        };
+
+        if !selection.part_1 && !selection.part_2 {
+            eprintln!("Neither part 1 nor part 2 specified");
+            return;
+        }
+
+        let input = or_exit(aoc::get_input(year, day, &source).await);
+
+        if format != Format::Plain {
+            let parsed = match aoc::parse(solver.as_ref(), &input) {
+                Ok(parsed) => parsed,
+                Err(err) => {
+                    aoc::report_error(day, None, &input, &err);
+                    std::process::exit(1);
+                }
+            };
+            let mut solutions = Vec::new();
+            let mut failed = false;
+
+            for part in selection.parts() {
+                match aoc::run_with_timeout(part, solver, &parsed, timeout) {
+                    Ok(solution) => solutions.push(solution),
+                    Err(err) => {
+                        aoc::report_run_error(day, part, &input, &err);
+                        failed = true;
+                    }
+                }
+            }
+
+            print_solutions(year, &solutions, ledger, format);
+
+            if failed {
+                std::process::exit(1);
+            }
+
+            return;
+        }
+
+        let Some(parsed) = aoc::parse_or_report(solver.as_ref(), &input) else {
+            std::process::exit(1);
+        };
+        let mut failed = false;
+
+        for part in selection.parts() {
+            failed |= aoc::solve(part, solver, &input, &parsed, ledger, timeout).is_none();
+        }
+
+        if failed {
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
+    if matches!(source, InputSource::File(_) | InputSource::Stdin) {
+        eprintln!("--input can only be used with a single day");
+        std::process::exit(1);
+    }
+
+    let inputs = get_inputs(dispatch_table, year, &days, &source).await;
+    let threads = pool::threads(jobs);
+    let start = Instant::now();
+
+    // Days are parsed, then their parts solved, each on the pool. Results are
+    // reported in day and part order once everything has finished.
+    let parsed = pool::map(&inputs, threads, |(solver, input)| {
+        panic::catch_unwind(AssertUnwindSafe(|| aoc::parse(solver.as_ref(), input)))
+    });
+    let mut parsed_days = Vec::new();
+    // Failed days don't stop the others, but still fail the run.
+    let mut failed = false;
+
+    for ((solver, input), parsed) in inputs.iter().zip(parsed) {
+        match parsed {
+            Ok(Ok(parsed)) => parsed_days.push((*solver, input, parsed)),
+            Ok(Err(err)) => {
+                aoc::report_error(solver.day(), None, input, &err);
+                failed = true;
+            }
+            Err(_) => {
+                eprintln!("Day {} parsing panicked", solver.day());
+                failed = true;
+            }
+        }
+    }
+
+    let parts: Vec<(usize, Part)> = (0..parsed_days.len())
+        .flat_map(|idx| selection.parts().into_iter().map(move |part| (idx, part)))
+        .collect();
+    let results = pool::map(&parts, threads, |(idx, part)| {
+        let (solver, _, parsed) = &parsed_days[*idx];
+        aoc::run_with_timeout(*part, solver, parsed, timeout)
+    });
+    let wall = start.elapsed();
+    let mut solutions = Vec::new();
+
+    for ((idx, part), result) in parts.into_iter().zip(results) {
+        let (solver, input, _) = &parsed_days[idx];
+
+        match result {
+            Ok(solution) => solutions.push(solution),
+            Err(err) => {
+                aoc::report_run_error(solver.day(), part, input, &err);
+                failed = true;
+            }
+        }
+    }
+
+    print_solutions(year, &solutions, ledger, format);
+
+    if format == Format::Plain {
+        println!(
+            "Wall-clock time: {} seconds on {} thread{}",
+            wall.as_secs_f64(),
+            threads,
+            if threads == 1 { "" } else { "s" }
+        );
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+async fn examples(year: i32, day: i32, html: Option<PathBuf>, force: bool) {
+    let html = match html {
+        Some(path) => or_exit(aoc::get_input(year, day, &path.into()).await),
+        None => or_exit(puzzle::download_puzzle(year, day).await),
+    };
+
+    let examples = fixtures::extract_examples(&html);
+
+    if examples.is_empty() {
+        eprintln!(
+            "No examples found on the puzzle page for day {} of {}",
+            day, year
+        );
+        std::process::exit(1);
+    }
+
+    for written in or_exit(fixtures::write_examples(
+        &fixtures::fixture_dir(year, day),
+        &examples,
+        force,
+    )) {
+        match written {
+            Written::Created(path) => println!("Wrote {}", path.display()),
+            Written::Skipped(path) => println!("Kept existing {}", path.display()),
+        }
+    }
+}
+
+async fn read(ledger: &Ledger, year: i32, day: i32, source: InputSource) {
+    let part_1_solved = ledger.is_solved(day, Part::Part1);
+    let html = or_exit(puzzle::get_puzzle(year, day, &source, part_1_solved).await);
+    let text = puzzle::render(&html);
 
-    let dispatch_table: HashMap<i32, Box<dyn Solver>> = HashMap::from([
-        (1, Box::new(Day1::new()) as Box<dyn Solver>),
-        (2, Box::new(Day2::new()) as Box<dyn Solver>),
-        (3, Box::new(Day3::new()) as Box<dyn Solver>),
-        (4, Box::new(Day4::new()) as Box<dyn Solver>),
-        (5, Box::new(Day5::new()) as Box<dyn Solver>),
-        (6, Box::new(Day6::new()) as Box<dyn Solver>),
-        (7, Box::new(Day7::new()) as Box<dyn Solver>),
-        (8, Box::new(Day8::new()) as Box<dyn Solver>),
-        (9, Box::new(Day9::new()) as Box<dyn Solver>),
-        (10, Box::new(Day10::new()) as Box<dyn Solver>),
+    if text.is_empty() {
+        eprintln!(
+            "No puzzle description found on the page for day {} of {}",
+            day, year
+        );
+        std::process::exit(1);
+    }
+
+    println!("{}", text);
+}
+
+async fn new(
+    dispatch_table: &DispatchTable,
+    ledger: &Ledger,
+    year: i32,
+    day: i32,
+    source: InputSource,
+) {
+    if dispatch_table.contains_key(&day) {
+        eprintln!("Day {} of {} is already registered", day, year);
+        std::process::exit(1);
+    }
+
+    // The day can still be set up without its examples, e.g. before it unlocks.
+    let part_1_solved = ledger.is_solved(day, Part::Part1);
+    let examples = match puzzle::get_puzzle(year, day, &source, part_1_solved).await {
+        Ok(html) => fixtures::extract_examples(&html),
+        Err(err) => {
+            eprintln!("Not adding example tests: {}", err);
+            Vec::new()
+        }
+    };
+
+    let path = or_exit(scaffold::create(Path::new("src"), year, day, &examples));
+    println!("Created {} and registered it in src/lib.rs", path.display());
+}
+
+struct WatchOptions {
+    parts: Vec<Part>,
+    interval: Duration,
+    timeout: Option<Duration>,
+}
+
+// Answers from before a restart, so the first run after one can still diff.
+const PREVIOUS_ANSWERS: &str = "AOC_WATCH_PREVIOUS";
+
+fn run_watched(
+    dispatch_table: &DispatchTable,
+    ledger: &Ledger,
+    year: i32,
+    day: i32,
+    options: &WatchOptions,
+    previous: &mut BTreeMap<Part, Answer>,
+) {
+    match fixtures::check(dispatch_table, year, day..=day, options.timeout) {
+        Ok(results) if results.is_empty() => println!("No examples found for day {}", day),
+        Ok(results) => fixtures::print_results(&results),
+        Err(err) => eprintln!("{}", err),
+    }
+
+    let solver = &dispatch_table[&day];
+    let input = match aoc::read_cached_input_for_day(year, day) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("{}", err);
+            return;
+        }
+    };
+
+    let parsed = match panic::catch_unwind(AssertUnwindSafe(|| aoc::parse(solver.as_ref(), &input)))
+    {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(err)) => return aoc::report_error(day, None, &input, &err),
+        Err(_) => return eprintln!("Day {} parsing panicked", day),
+    };
+
+    for &part in &options.parts {
+        match aoc::run_with_timeout(part, solver, &parsed, options.timeout) {
+            Ok(solution) => {
+                println!("Part {}: {}", part, solution.answer);
+                println!(
+                    "{}",
+                    watch::describe_change(previous.get(&part), &solution.answer)
+                );
+                println!("Solution took {} seconds", solution.solve.as_secs_f64());
+                println!("{}", ledger.check(day, part, &solution.answer));
+                previous.insert(part, solution.answer);
+            }
+            Err(err) => aoc::report_run_error(day, part, &input, &err),
+        }
+    }
+}
+
+// New source needs a new build, so the watcher rebuilds and replaces itself
+// with the result. A failed build leaves the old one watching.
+fn restart(previous: &BTreeMap<Part, Answer>) {
+    let exe = or_exit(std::env::current_exe());
+    let mut build = process::Command::new("cargo");
+    build.arg("build");
+
+    if exe.components().any(|c| c.as_os_str() == "release") {
+        build.arg("--release");
+    }
+
+    match build.status() {
+        Ok(status) if status.success() => {}
+        Ok(_) => return eprintln!("Build failed; waiting for the next change"),
+        Err(err) => return eprintln!("Failed to run cargo: {}", err),
+    }
+
+    let mut command = process::Command::new(exe);
+    command
+        .args(std::env::args_os().skip(1))
+        .env(PREVIOUS_ANSWERS, serde_json::to_string(previous).unwrap());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        eprintln!("Failed to restart: {}", command.exec());
+    }
+
+    #[cfg(not(unix))]
+    {
+        drop(command);
+        eprintln!("Rebuilt; restart watch to pick up the changes");
+    }
+}
+
+async fn watch(
+    dispatch_table: &DispatchTable,
+    ledger: &Ledger,
+    year: i32,
+    day: i32,
+    options: WatchOptions,
+    source: InputSource,
+) {
+    if !dispatch_table.contains_key(&day) {
+        eprintln!("Day {} of {} is not yet implemented.", day, year);
+        std::process::exit(1);
+    }
+
+    // Only the first run may download the input; later ones read the cache.
+    if let Err(err) = aoc::get_input(year, day, &source).await {
+        eprintln!("{}", err);
+    }
+
+    let mut previous: BTreeMap<Part, Answer> = std::env::var(PREVIOUS_ANSWERS)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    let mut watcher = Watcher::new(vec![
+        aoc::input_cache_path(year, day),
+        fixtures::fixture_dir(year, day),
+        PathBuf::from("src"),
     ]);
 
-    if let Some(solver) = dispatch_table.get(&args.day) {
-        if args.part_1 {
-            aoc::solve(aoc::Part::Part1, solver).await?;
+    run_watched(dispatch_table, ledger, year, day, &options, &mut previous);
+    println!("Watching for changes; press Ctrl-C to stop");
+
+    loop {
+        tokio::time::sleep(options.interval).await;
+        let changed = watcher.changed();
+
+        if changed.is_empty() {
+            continue;
         }
 
-        if args.part_2 {
-            aoc::solve(aoc::Part::Part2, solver).await?;
+        println!();
+        for path in &changed {
+            println!("Changed: {}", path.display());
         }
 
-        if !args.part_1 && !args.part_2 {
-            eprintln!("Neither part 1 nor part 2 specified");
+        if changed.iter().any(|path| path.starts_with("src")) {
+            restart(&previous);
+        } else {
+            run_watched(dispatch_table, ledger, year, day, &options, &mut previous);
+        }
+    }
+}
+
+async fn leaderboard(
+    year: i32,
+    id: Option<u64>,
+    day: Option<i32>,
+    offline: bool,
+    file: Option<PathBuf>,
+) {
+    let leaderboard = match (file, id) {
+        (Some(path), _) => or_exit(leaderboard::read_file(&path)),
+        (None, Some(id)) => {
+            let (leaderboard, age) = or_exit(leaderboard::get_leaderboard(year, id, offline).await);
+            if let Some(age) = age {
+                eprintln!("Using the copy cached {} minutes ago", age.as_secs() / 60);
+            }
+            leaderboard
         }
+        (None, None) => unreachable!("clap requires an id or a file"),
+    };
+
+    println!("{}", leaderboard::render(&leaderboard, day));
+}
+
+fn test(
+    dispatch_table: &DispatchTable,
+    ledger: &Ledger,
+    year: i32,
+    days: RangeInclusive<i32>,
+    real: bool,
+    timeout: Option<Duration>,
+) {
+    let results = if real {
+        or_exit(fixtures::check_inputs(
+            dispatch_table,
+            year,
+            days,
+            ledger,
+            timeout,
+        ))
     } else {
-        eprintln!("Day {} is not yet implemented.", args.day);
+        or_exit(fixtures::check(dispatch_table, year, days, timeout))
+    };
+
+    if results.is_empty() {
+        if real {
+            println!("No cached inputs with known answers found");
+        } else {
+            println!("No fixtures with expected answers found");
+        }
+        return;
+    }
+
+    fixtures::print_results(&results);
+
+    if !results.iter().all(|r| r.passed()) {
+        std::process::exit(1);
+    }
+}
+
+struct BenchOptions {
+    iterations: usize,
+    warmup: usize,
+    json: Option<PathBuf>,
+    save: bool,
+    compare: bool,
+    baseline: Option<String>,
+    threshold: f64,
+}
+
+async fn bench(
+    dispatch_table: &DispatchTable,
+    year: i32,
+    selection: Selection,
+    source: InputSource,
+    options: BenchOptions,
+) {
+    let mut results = Vec::new();
+
+    for (solver, input) in get_inputs(dispatch_table, year, &selection.days(), &source).await {
+        let solver = solver.as_ref();
+        let parsed = panic::catch_unwind(AssertUnwindSafe(|| {
+            bench::bench_parse(solver, &input, options.warmup, options.iterations)
+        }));
+
+        let (parsed, parse) = match parsed {
+            Ok(Ok(parsed)) => parsed,
+            Ok(Err(err)) => {
+                aoc::report_error(solver.day(), None, &input, &err);
+                continue;
+            }
+            Err(_) => {
+                eprintln!("Day {} parsing panicked", solver.day());
+                continue;
+            }
+        };
+
+        for part in selection.parts() {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                bench::bench(
+                    part,
+                    solver,
+                    &parsed,
+                    parse,
+                    options.warmup,
+                    options.iterations,
+                )
+            }));
+
+            match result {
+                Ok(Ok(result)) => results.push(result),
+                Ok(Err(err)) => aoc::report_error(solver.day(), Some(part), &input, &err),
+                Err(_) => eprintln!("Day {} part {} panicked", solver.day(), part),
+            }
+        }
+    }
+
+    // Keep stdout parseable when the JSON is going there.
+    let json_to_stdout = options
+        .json
+        .as_ref()
+        .is_some_and(|path| path.as_os_str() == "-");
+
+    if !json_to_stdout {
+        bench::print_results(&results);
+    }
+    if let Some(path) = &options.json {
+        or_exit(bench::write_json(path, &results));
+    }
+
+    let history_path = bench::history_path(year);
+    let commit = bench::current_commit();
+
+    // Saving first is safe: by default the current commit is never its own baseline.
+    if options.save {
+        or_exit(bench::append_history(&history_path, &commit, &results));
+    }
+
+    if options.compare {
+        let history = or_exit(bench::load_history(&history_path));
+        let comparisons = bench::compare(
+            &results,
+            &history,
+            &commit,
+            options.baseline.as_deref(),
+            options.threshold,
+        );
+
+        let table = bench::render_comparisons(&comparisons);
+
+        if json_to_stdout {
+            eprintln!("{}", table);
+        } else {
+            println!("\n{}", table);
+        }
+
+        if comparisons.iter().any(|c| c.regressed) {
+            eprintln!(
+                "Some solvers are more than {}% slower than the baseline",
+                options.threshold
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let mut registry = registry();
+
+    if args.list {
+        for (year, dispatch_table) in &registry {
+            for day in dispatch_table.keys() {
+                println!("{} {}", year, day);
+            }
+        }
+
+        return Ok(());
+    }
+
+    let Some(year) = args.year.or_else(|| registry.keys().next_back().copied()) else {
+        eprintln!("No solvers are registered, so --year is required");
+        std::process::exit(1);
+    };
+    let dispatch_table = registry.remove(&year).unwrap_or_default();
+    let mut ledger = or_exit(Ledger::load(&ledger::ledger_path(year)));
+
+    match args.command {
+        Some(Command::Submit {
+            day,
+            part,
+            answer,
+            base_url,
+        }) => {
+            let part = if part == 1 { Part::Part1 } else { Part::Part2 };
+            let answer = answer.map(Answer::from);
+            submit(
+                &dispatch_table,
+                &mut ledger,
+                year,
+                day,
+                part,
+                answer,
+                &base_url,
+            )
+            .await;
+        }
+        Some(Command::Examples { day, html, force }) => {
+            examples(year, day, html, force).await;
+        }
+        Some(Command::Read {
+            day,
+            download,
+            html,
+        }) => {
+            let source = match html {
+                Some(path) => path.into(),
+                None => download.source(),
+            };
+            read(&ledger, year, day, source).await;
+        }
+        Some(Command::New { day, download }) => {
+            new(&dispatch_table, &ledger, year, day, download.source()).await;
+        }
+        Some(Command::Watch {
+            day,
+            part_1,
+            part_2,
+            interval,
+            timeout,
+            download,
+        }) => {
+            watch(
+                &dispatch_table,
+                &ledger,
+                year,
+                day,
+                WatchOptions {
+                    parts: parts(part_1, part_2),
+                    interval: Duration::from_millis(interval),
+                    timeout,
+                },
+                download.source(),
+            )
+            .await;
+        }
+        Some(Command::Leaderboard {
+            id,
+            day,
+            offline,
+            file,
+        }) => {
+            leaderboard(year, id, day, offline, file).await;
+        }
+        Some(Command::Test { day, real, timeout }) => {
+            test(
+                &dispatch_table,
+                &ledger,
+                year,
+                day.unwrap_or(1..=25),
+                real,
+                timeout,
+            );
+        }
+        Some(Command::Bench {
+            selection,
+            download,
+            iterations,
+            warmup,
+            json,
+            save,
+            compare,
+            baseline,
+            threshold,
+        }) => {
+            let options = BenchOptions {
+                iterations,
+                warmup,
+                json,
+                save,
+                compare,
+                baseline,
+                threshold,
+            };
+            bench(&dispatch_table, year, selection, download.source(), options).await;
+        }
+        None => {
+            let source = match args.input {
+                Some(path) => path.into(),
+                None => args.download.source(),
+            };
+            solve(
+                &dispatch_table,
+                &ledger,
+                year,
+                args.selection,
+                source,
+                SolveOptions {
+                    jobs: args.jobs,
+                    format: args.format,
+                    timeout: args.timeout,
+                },
+            )
+            .await;
+        }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::parse_days;
+
+    #[test]
+    fn parses_day_ranges() {
+        assert_eq!(parse_days("3"), Ok(3..=3));
+        assert_eq!(parse_days("3..7"), Ok(3..=6));
+        assert_eq!(parse_days("3..=7"), Ok(3..=7));
+        assert_eq!(parse_days("3..4"), Ok(3..=3));
+        assert!(parse_days("3..3").is_err());
+        assert!(parse_days("7..3").is_err());
+        assert!(parse_days("7..=3").is_err());
+        assert!(parse_days("x").is_err());
+    }
+}
@@ -0,0 +1,72 @@
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+// Zero means one thread per core.
+pub fn threads(jobs: usize) -> usize {
+    match jobs {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        jobs => jobs,
+    }
+}
+
+// Maps items on up to `threads` threads, returning the results in the same
+// order as the items no matter which finishes first.
+pub fn map<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.min(items.len()) {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(idx) else {
+                    break;
+                };
+                let result = f(item);
+                results.lock().unwrap()[idx] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every item was mapped"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::map;
+
+    #[test]
+    fn keeps_item_order() {
+        let items: Vec<u64> = (0..20).collect();
+        // Earlier items take longer, so they finish last.
+        let results = map(&items, 4, |n| {
+            thread::sleep(Duration::from_millis(20 - n));
+            n * 2
+        });
+
+        assert_eq!(results, (0..20).map(|n| n * 2).collect::<Vec<u64>>());
+        assert_eq!(map(&items, 1, |n| n + 1)[19], 20);
+    }
+}
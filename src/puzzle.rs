@@ -0,0 +1,233 @@
+use std::path::PathBuf;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{
+    aoc::{self, InputError, InputSource, BASE_URL},
+    http,
+};
+
+const WIDTH: usize = 80;
+
+fn puzzle_cache_path(year: i32, day: i32) -> PathBuf {
+    PathBuf::from(format!("puzzles/{}/day{:02}.html", year, day))
+}
+
+// Part 2 of a puzzle only shows up on its page for a logged in user who has
+// solved part 1, so the session is sent when there is one.
+pub async fn download_puzzle(year: i32, day: i32) -> Result<String, InputError> {
+    let url = format!("{}/{}/day/{}", BASE_URL, year, day);
+
+    http::get(&url, aoc::session_id().as_deref())
+        .await
+        .map_err(InputError::PuzzleHttp)
+}
+
+// Part 2 gets its own article once it shows up on the page.
+pub fn has_part_2(html: &str) -> bool {
+    articles(html).count() > 1
+}
+
+// Works like aoc::get_input, but for the puzzle page. A cached page is
+// missing part 2 if it was saved before part 1 was solved, so it is fetched
+// again once the ledger knows part 1 is solved.
+pub async fn get_puzzle(
+    year: i32,
+    day: i32,
+    source: &InputSource,
+    part_1_solved: bool,
+) -> Result<String, InputError> {
+    let path = puzzle_cache_path(year, day);
+
+    match source {
+        InputSource::Puzzle { refresh } => {
+            if !refresh && path.exists() {
+                let html = aoc::read_file(path.clone())?;
+
+                if has_part_2(&html) || !part_1_solved {
+                    return Ok(html);
+                }
+            }
+
+            let html = download_puzzle(year, day).await?;
+            aoc::write_file(path, &html)?;
+            Ok(html)
+        }
+        InputSource::Cached if !path.exists() => Err(InputError::NotCached(path)),
+        InputSource::Cached => {
+            let html = aoc::read_file(path)?;
+
+            if part_1_solved && !has_part_2(&html) {
+                eprintln!("The cached page has no part 2 yet; run without --offline to fetch it");
+            }
+
+            Ok(html)
+        }
+        InputSource::File(_) | InputSource::Stdin => aoc::get_input(year, day, source).await,
+    }
+}
+
+pub fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&")
+}
+
+static SECTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<article[^>]*>(?P<article>.*?)</article>|<p>(?P<answer>Your puzzle answer was.*?)</p>")
+        .unwrap()
+});
+static BLOCK_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?s)<h2[^>]*>(?P<h2>.*?)</h2>|<pre><code>(?P<pre>.*?)</code></pre>|<p>(?P<p>.*?)</p>|<ul>(?P<ul>.*?)</ul>",
+    )
+    .unwrap()
+});
+static ITEM_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<li>(?P<li>.*?)</li>").unwrap());
+static EM_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"</?em[^>]*>").unwrap());
+static ARTICLE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<article[^>]*>(?P<body>.*?)</article>").unwrap());
+static TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]*>").unwrap());
+
+// The bodies of the page's articles: the puzzle's parts, or the message
+// after submitting an answer.
+pub fn articles(html: &str) -> impl Iterator<Item = &str> {
+    ARTICLE_REGEX
+        .captures_iter(html)
+        .map(|article| article.name("body").unwrap().as_str())
+}
+
+pub fn strip_tags(html: &str) -> String {
+    decode_entities(&TAG_REGEX.replace_all(html, ""))
+}
+
+// Emphasis, which the site uses to highlight key numbers, is kept as *text*.
+fn inline_text(html: &str) -> String {
+    let text = EM_REGEX.replace_all(html, "*");
+    strip_tags(&text)
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+fn wrap(text: &str, first_indent: &str, indent: &str) -> String {
+    let mut lines = Vec::new();
+    let mut line = first_indent.to_owned();
+
+    for word in text.split(' ') {
+        let prefix = if lines.is_empty() {
+            first_indent
+        } else {
+            indent
+        };
+
+        if line.len() > prefix.len() && line.len() + 1 + word.len() > WIDTH {
+            lines.push(line);
+            line = indent.to_owned();
+        }
+
+        if line.len() > prefix.len() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+
+    lines.push(line);
+    lines.join("\n")
+}
+
+fn render_article(html: &str) -> Vec<String> {
+    BLOCK_REGEX
+        .captures_iter(html)
+        .map(|block| {
+            if let Some(h2) = block.name("h2") {
+                inline_text(h2.as_str())
+            } else if let Some(pre) = block.name("pre") {
+                strip_tags(pre.as_str())
+                    .trim_end()
+                    .lines()
+                    .map(|l| format!("    {}", l))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            } else if let Some(p) = block.name("p") {
+                wrap(&inline_text(p.as_str()), "", "")
+            } else {
+                ITEM_REGEX
+                    .captures_iter(&block["ul"])
+                    .map(|item| wrap(&inline_text(&item["li"]), "  - ", "    "))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            }
+        })
+        .collect()
+}
+
+pub fn render(html: &str) -> String {
+    let blocks: Vec<String> = SECTION_REGEX
+        .captures_iter(html)
+        .flat_map(|section| match section.name("article") {
+            Some(article) => render_article(article.as_str()),
+            None => vec![inline_text(&section["answer"])],
+        })
+        .collect();
+
+    blocks.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{has_part_2, render};
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is wrong with global snow production, and you've been selected to take a look. The Elves have even given you a map; on it, they've used <em>stars</em> to mark the top fifty locations.</p>
+<p>For example:</p>
+<pre><code>1abc2
+<em>pqr3stu8vwx</em>
+</code></pre>
+<ul><li>In this example, the calibration values of these four lines are <code>12</code> and <code>38</code>.</li></ul>
+<p>Adding these together produces <code><em>50</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54601</code>.</p><p class="day-success">The first half of this puzzle is complete!</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>It looks like some of the digits are <a href="/x">spelled out</a> &amp; count.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn renders_puzzle_page() {
+        assert_eq!(
+            render(PAGE),
+            "--- Day 1: Trebuchet?! ---
+
+Something is wrong with global snow production, and you've been selected to take
+a look. The Elves have even given you a map; on it, they've used *stars* to mark
+the top fifty locations.
+
+For example:
+
+    1abc2
+    pqr3stu8vwx
+
+  - In this example, the calibration values of these four lines are 12 and 38.
+
+Adding these together produces *50*.
+
+Your puzzle answer was 54601.
+
+--- Part Two ---
+
+It looks like some of the digits are spelled out & count."
+        );
+    }
+
+    #[test]
+    fn notices_missing_part_2() {
+        assert!(has_part_2(PAGE));
+        assert!(!has_part_2(
+            &PAGE[..PAGE.find("<p>Your puzzle answer").unwrap()]
+        ));
+    }
+}
@@ -0,0 +1,267 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    aoc::{Answer, Part},
+    fixtures::Example,
+};
+
+const DAYS_MACRO: &str = "days! {\n";
+
+// Whether a module's solver reports the given year, going by its source.
+fn is_for_year(source: &str, year: i32) -> bool {
+    let source: String = source.split_whitespace().collect();
+    source.contains(&format!("fnyear(&self)->i32{{{}}}", year))
+}
+
+// Days of another year that already have a dayN module get the year in
+// their module name too. Modules written since the last build are not in
+// the registry yet, so existing ones are checked for the year as well.
+fn module_name(src: &Path, year: i32, day: i32) -> io::Result<Option<String>> {
+    for name in [format!("day{}", day), format!("day{}_{}", day, year)] {
+        match fs::read_to_string(src.join(format!("{}.rs", name))) {
+            Ok(source) if is_for_year(&source, year) => return Ok(None),
+            Ok(_) => continue,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Some(name)),
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(None)
+}
+
+// A raw literal needs one more # than the longest run of them after a quote
+// in the text, or the text would end it early.
+fn string_literal(text: &str) -> String {
+    if !text.contains('"') && !text.contains('\\') {
+        return format!("\"{}\"", text);
+    }
+
+    let longest = text
+        .split('"')
+        .skip(1)
+        .map(|after| after.len() - after.trim_start_matches('#').len())
+        .max()
+        .unwrap_or(0);
+    let hashes = "#".repeat(longest + 1);
+
+    format!("r{}\"{}\"{}", hashes, text, hashes)
+}
+
+fn answer_literal(answer: &Answer) -> String {
+    match answer {
+        Answer::Integer(n) => n.to_string(),
+        Answer::BigInteger(n) => format!("{}i128", n),
+        Answer::Text(_) | Answer::Grid(_) => format!("{:?}", answer.to_string()),
+    }
+}
+
+fn render_tests(solver: &str, examples: &[Example]) -> String {
+    let parts: Vec<(Part, usize, &Answer)> = [Part::Part1, Part::Part2]
+        .into_iter()
+        .filter_map(|part| {
+            examples
+                .iter()
+                .enumerate()
+                .find_map(|(idx, example)| example.answers.get(part).map(|a| (part, idx, a)))
+        })
+        .collect();
+
+    if parts.is_empty() {
+        return String::new();
+    }
+
+    let mut used: Vec<usize> = parts.iter().map(|(_, idx, _)| *idx).collect();
+    used.dedup();
+    let name = |idx: usize| {
+        if used.len() == 1 {
+            "INPUT".to_owned()
+        } else {
+            format!("EXAMPLE_{}", idx + 1)
+        }
+    };
+
+    let mut tests = format!(
+        "\n#[cfg(test)]\nmod tests {{\n    use super::{};\n    use crate::aoc::{{Answer, Solver}};\n",
+        solver
+    );
+
+    for idx in &used {
+        tests.push_str(&format!(
+            "\n    const {}: &str = {};\n",
+            name(*idx),
+            string_literal(examples[*idx].input.trim_end_matches('\n'))
+        ));
+    }
+
+    for (part, idx, answer) in parts {
+        tests.push_str(&format!(
+            "
+    #[test]
+    fn part_{part}() {{
+        let solver = {solver}::new();
+        assert_eq!(
+            solver.solve_part_{part}(&solver.parse({input}).unwrap()),
+            Ok(Answer::from({answer}))
+        );
+    }}
+",
+            part = part,
+            solver = solver,
+            input = name(idx),
+            answer = answer_literal(answer)
+        ));
+    }
+
+    tests.push_str("}\n");
+    tests
+}
+
+pub fn render_day(year: i32, day: i32, examples: &[Example]) -> String {
+    let solver = format!("Day{}", day);
+
+    format!(
+        "use crate::aoc::{{Answer, SolveError, Solver}};
+
+#[derive(Default)]
+pub struct {solver} {{}}
+
+impl {solver} {{
+    pub fn new() -> Self {{
+        {solver} {{}}
+    }}
+}}
+
+impl Solver for {solver} {{
+    type Input = Vec<String>;
+
+    fn year(&self) -> i32 {{
+        {year}
+    }}
+
+    fn day(&self) -> i32 {{
+        {day}
+    }}
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {{
+        Ok(input.lines().map(str::to_owned).collect())
+    }}
+
+    fn solve_part_1(&self, _input: &Self::Input) -> Result<Answer, SolveError> {{
+        todo!()
+    }}
+
+    fn solve_part_2(&self, _input: &Self::Input) -> Result<Answer, SolveError> {{
+        todo!()
+    }}
+}}
+{tests}",
+        solver = solver,
+        year = year,
+        day = day,
+        tests = render_tests(&solver, examples)
+    )
+}
+
+// Adds the module to the end of the days! list in lib.rs.
+pub fn register(lib: &str, module: &str, solver: &str) -> Option<String> {
+    let start = lib.find(DAYS_MACRO)? + DAYS_MACRO.len();
+    let end = start + lib[start..].find("\n}")? + 1;
+
+    Some(format!(
+        "{}    {}::{},\n{}",
+        &lib[..end],
+        module,
+        solver,
+        &lib[end..]
+    ))
+}
+
+// Writes the new day's module into src and registers it, returning the
+// path of the module.
+pub fn create(src: &Path, year: i32, day: i32, examples: &[Example]) -> io::Result<PathBuf> {
+    let module = module_name(src, year, day)?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("a module for day {} of {} already exists", day, year),
+        )
+    })?;
+
+    let lib_path = src.join("lib.rs");
+    let lib = register(
+        &fs::read_to_string(&lib_path)?,
+        &module,
+        &format!("Day{}", day),
+    )
+    .ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("no days! list found in {}", lib_path.display()),
+        )
+    })?;
+
+    let path = src.join(format!("{}.rs", module));
+    fs::write(&path, render_day(year, day, examples))?;
+    fs::write(&lib_path, lib)?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{register, render_day, string_literal};
+    use crate::{aoc::Answer, fixtures::Answers, fixtures::Example};
+
+    #[test]
+    fn registers_module() {
+        let lib = "pub mod aoc;\n\ndays! {\n    day1::Day1,\n    day2::Day2,\n}\n";
+
+        assert_eq!(
+            register(lib, "day3", "Day3").unwrap(),
+            "pub mod aoc;\n\ndays! {\n    day1::Day1,\n    day2::Day2,\n    day3::Day3,\n}\n"
+        );
+        assert_eq!(register("pub mod aoc;\n", "day3", "Day3"), None);
+    }
+
+    #[test]
+    fn renders_tests_from_examples() {
+        let examples = vec![
+            Example {
+                input: "1 2\n3 4\n".to_owned(),
+                answers: Answers {
+                    part_1: Some(Answer::from(10)),
+                    part_2: None,
+                },
+            },
+            Example {
+                input: "say \"hi\"\n".to_owned(),
+                answers: Answers {
+                    part_1: None,
+                    part_2: Some(Answer::from("hi")),
+                },
+            },
+        ];
+        let day = render_day(2024, 3, &examples);
+
+        assert!(day.contains("pub struct Day3 {}"));
+        assert!(day.contains("fn year(&self) -> i32 {\n        2024\n    }"));
+        assert!(day.contains("const EXAMPLE_1: &str = \"1 2\n3 4\";"));
+        assert!(day.contains("const EXAMPLE_2: &str = r#\"say \"hi\"\"#;"));
+        assert!(day.contains(
+            "solver.solve_part_1(&solver.parse(EXAMPLE_1).unwrap()),\n            Ok(Answer::from(10))"
+        ));
+        assert!(day.contains(
+            "solver.solve_part_2(&solver.parse(EXAMPLE_2).unwrap()),\n            Ok(Answer::from(\"hi\"))"
+        ));
+        assert!(!render_day(2024, 3, &[]).contains("mod tests"));
+    }
+
+    #[test]
+    fn quotes_text_with_hashes() {
+        assert_eq!(string_literal("#.#\n..."), "\"#.#\n...\"");
+        assert_eq!(string_literal("say \"hi\""), "r#\"say \"hi\"\"#");
+        assert_eq!(string_literal("\"#.\"##"), "r###\"\"#.\"##\"###");
+    }
+}
@@ -0,0 +1,253 @@
+use std::{error::Error, fmt::Display, time::Duration};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    aoc::{Answer, MissingSessionId, Part},
+    http::{self, HttpError},
+    puzzle,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SubmissionResult {
+    Correct,
+    Incorrect(Option<Hint>),
+    TooRecent(Option<Duration>),
+    AlreadySolved,
+    Unknown(String),
+}
+
+impl Display for SubmissionResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "That's the right answer!"),
+            Self::Incorrect(None) => write!(f, "That's not the right answer."),
+            Self::Incorrect(Some(Hint::TooHigh)) => {
+                write!(f, "That's not the right answer; your answer is too high.")
+            }
+            Self::Incorrect(Some(Hint::TooLow)) => {
+                write!(f, "That's not the right answer; your answer is too low.")
+            }
+            Self::TooRecent(None) => write!(f, "You gave an answer too recently."),
+            Self::TooRecent(Some(wait)) => write!(
+                f,
+                "You gave an answer too recently; wait {}s before trying again.",
+                wait.as_secs()
+            ),
+            Self::AlreadySolved => write!(f, "This part has already been solved."),
+            Self::Unknown(text) => write!(f, "Unrecognized response: {}", text),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Session(MissingSessionId),
+    Http(HttpError),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Session(err) => write!(f, "{} and is required to submit", err),
+            Self::Http(err) => write!(f, "Failed to submit answer: {}", err),
+        }
+    }
+}
+
+impl Error for SubmitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Session(err) => Some(err),
+            Self::Http(err) => Some(err),
+        }
+    }
+}
+
+impl From<HttpError> for SubmitError {
+    fn from(value: HttpError) -> Self {
+        Self::Http(value)
+    }
+}
+
+impl From<MissingSessionId> for SubmitError {
+    fn from(value: MissingSessionId) -> Self {
+        Self::Session(value)
+    }
+}
+
+static WAIT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"You have (?:(?P<minutes>\d+)m )?(?P<seconds>\d+)s left to wait").unwrap()
+});
+
+fn article_text(html: &str) -> String {
+    let body = puzzle::articles(html).next().unwrap_or(html);
+
+    puzzle::strip_tags(body)
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+pub fn parse_response(html: &str) -> SubmissionResult {
+    let text = article_text(html);
+
+    if text.contains("That's the right answer") {
+        SubmissionResult::Correct
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+
+        SubmissionResult::Incorrect(hint)
+    } else if text.contains("You gave an answer too recently") {
+        let wait = WAIT_REGEX.captures(&text).map(|c| {
+            let minutes: u64 = c.name("minutes").map_or(0, |m| m.as_str().parse().unwrap());
+            let seconds: u64 = c["seconds"].parse().unwrap();
+            Duration::from_secs(minutes * 60 + seconds)
+        });
+
+        SubmissionResult::TooRecent(wait)
+    } else if text.contains("You don't seem to be solving the right level") {
+        SubmissionResult::AlreadySolved
+    } else {
+        SubmissionResult::Unknown(text)
+    }
+}
+
+pub async fn submit_answer(
+    base_url: &str,
+    session_id: &str,
+    year: i32,
+    day: i32,
+    part: Part,
+    answer: &Answer,
+) -> Result<SubmissionResult, SubmitError> {
+    let url = format!(
+        "{}/{}/day/{}/answer",
+        base_url.trim_end_matches('/'),
+        year,
+        day
+    );
+    let level = part.to_string();
+    let answer = answer.to_string();
+
+    let html = http::post_form(
+        &url,
+        Some(session_id),
+        &[("level", level.as_str()), ("answer", answer.as_str())],
+    )
+    .await?;
+
+    Ok(parse_response(&html))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+        time::Duration,
+    };
+
+    use super::{parse_response, submit_answer, Hint, SubmissionResult};
+    use crate::aoc::{Answer, Part};
+
+    fn page(article: &str) -> String {
+        format!(
+            "<html><body><main><article><p>{}</p></article></main></body></html>",
+            article
+        )
+    }
+
+    #[test]
+    fn parses_responses() {
+        assert_eq!(
+            parse_response(&page(
+                "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer."
+            )),
+            SubmissionResult::Correct
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too high.  Please wait one minute before trying again."
+            )),
+            SubmissionResult::Incorrect(Some(Hint::TooHigh))
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            SubmissionResult::Incorrect(Some(Hint::TooLow))
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer.  If you're stuck, make sure you're using the full input data."
+            )),
+            SubmissionResult::Incorrect(None)
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 12s left to wait."
+            )),
+            SubmissionResult::TooRecent(Some(Duration::from_secs(72)))
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            SubmissionResult::AlreadySolved
+        );
+    }
+
+    #[tokio::test]
+    async fn submits_to_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+
+            while !String::from_utf8_lossy(&request).contains("answer=") {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+
+            let body = page("That's the right answer!");
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+
+            String::from_utf8(request).unwrap()
+        });
+
+        let result = submit_answer(&base_url, "abc", 2023, 5, Part::Part2, &Answer::from(46))
+            .await
+            .unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(result, SubmissionResult::Correct);
+        assert!(request.starts_with("POST /2023/day/5/answer "));
+        assert!(request.contains("session=abc"));
+        assert!(request.contains("level=2&answer=46"));
+    }
+}
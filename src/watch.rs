@@ -0,0 +1,144 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::aoc::Answer;
+
+type Stamp = (SystemTime, u64);
+
+// Polls files for changes. A directory stands for the files directly in it,
+// so files added to or removed from it count as changes too.
+pub struct Watcher {
+    roots: Vec<PathBuf>,
+    stamps: BTreeMap<PathBuf, Stamp>,
+}
+
+fn stamp(path: &Path) -> Option<Stamp> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+fn snapshot(roots: &[PathBuf]) -> BTreeMap<PathBuf, Stamp> {
+    let mut stamps = BTreeMap::new();
+
+    for root in roots {
+        let files: Vec<PathBuf> = match fs::read_dir(root) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.is_file())
+                .collect(),
+            Err(_) => vec![root.clone()],
+        };
+
+        for file in files {
+            if let Some(stamp) = stamp(&file) {
+                stamps.insert(file, stamp);
+            }
+        }
+    }
+
+    stamps
+}
+
+impl Watcher {
+    pub fn new(roots: Vec<PathBuf>) -> Self {
+        let stamps = snapshot(&roots);
+        Watcher { roots, stamps }
+    }
+
+    // Returns the files that were modified, added or removed since the last
+    // call.
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let stamps = snapshot(&self.roots);
+        let mut changed: Vec<PathBuf> = stamps
+            .iter()
+            .filter(|(path, stamp)| self.stamps.get(*path) != Some(stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        changed.extend(
+            self.stamps
+                .keys()
+                .filter(|path| !stamps.contains_key(*path))
+                .cloned(),
+        );
+
+        self.stamps = stamps;
+        changed
+    }
+}
+
+pub fn describe_change(previous: Option<&Answer>, current: &Answer) -> String {
+    match previous {
+        None => "new".to_owned(),
+        Some(previous) if previous == current => "unchanged".to_owned(),
+        Some(Answer::Grid(previous)) => {
+            let current = current.to_string();
+            let current: Vec<&str> = current.lines().collect();
+            let mut lines = vec!["changed:".to_owned()];
+
+            for idx in 0..previous.len().max(current.len()) {
+                match (previous.get(idx), current.get(idx)) {
+                    (Some(a), Some(b)) if a == b => lines.push(format!("  {}", a)),
+                    (a, b) => {
+                        lines.extend(a.map(|a| format!("- {}", a)));
+                        lines.extend(b.map(|b| format!("+ {}", b)));
+                    }
+                }
+            }
+
+            lines.join("\n")
+        }
+        Some(previous) => format!("changed from {}", previous),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{describe_change, Watcher};
+    use crate::aoc::Answer;
+
+    #[test]
+    fn notices_changed_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("example1.txt");
+        fs::write(&input, "1").unwrap();
+
+        let mut watcher = Watcher::new(vec![dir.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::write(&input, "1 2").unwrap();
+        assert_eq!(watcher.changed(), vec![input.clone()]);
+
+        let added = dir.join("example2.txt");
+        fs::write(&added, "3").unwrap();
+        assert_eq!(watcher.changed(), vec![added]);
+
+        fs::remove_file(&input).unwrap();
+        assert_eq!(watcher.changed(), vec![input]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn describes_answer_changes() {
+        let answer = Answer::from(46);
+
+        assert_eq!(describe_change(None, &answer), "new");
+        assert_eq!(describe_change(Some(&answer), &answer), "unchanged");
+        assert_eq!(
+            describe_change(Some(&Answer::from(35)), &answer),
+            "changed from 35"
+        );
+        assert_eq!(
+            describe_change(Some(&Answer::from("#.\n.#")), &Answer::from("#.\n##")),
+            "changed:\n  #.\n- .#\n+ ##"
+        );
+    }
+}